@@ -5,7 +5,10 @@
 use std::time::Duration;
 
 use eframe::{egui, NativeOptions};
-use egui_task_manager::{executors, Caller, Handler, Progress, Task, TaskManager, TasksCollection};
+use egui_task_manager::{
+    executors, Caller, Contexts, Handler, Progress, ProgressState, Task, TaskManager,
+    TasksCollection,
+};
 
 fn main() -> Result<(), eframe::Error> {
     egui_task_manager::setup!();
@@ -56,8 +59,8 @@ impl<'c> TasksCollection<'c> for LabelCollection {
 struct UnitProgress;
 
 impl Progress for UnitProgress {
-    fn apply(&self, current: &mut u32) {
-        *current += 1;
+    fn apply(&self, state: &mut ProgressState) {
+        state.advance(1);
     }
 }
 
@@ -74,8 +77,13 @@ struct MyApp {
 
 impl Default for MyApp {
     fn default() -> Self {
+        let mut manager = TaskManager::new();
+        manager
+            .register_collection::<SimpleCollection>()
+            .register_collection::<LabelCollection>();
+
         Self {
-            manager: TaskManager::new(),
+            manager,
             num: 0,
             task_num: 1,
             task_name: "New task".to_owned(),
@@ -87,9 +95,11 @@ impl Default for MyApp {
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
-        self.manager
-            .add_collection::<SimpleCollection>(&mut self.num)
-            .add_collection::<LabelCollection>(&mut self.current_label);
+        self.manager.process(
+            Contexts::new()
+                .with::<SimpleCollection>(&mut self.num)
+                .with::<LabelCollection>(&mut self.current_label),
+        );
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.group(|ui| {
@@ -120,8 +130,9 @@ impl eframe::App for MyApp {
                     let label = self.label_to_set.clone();
                     let caller = Caller::progressing(|progress| async move {
                         let _ = progress.set_total(10);
-                        for _ in 0..10 {
+                        for step in 1..=10 {
                             let _ = progress.update(UnitProgress);
+                            progress.info(format!("Step {step} is done"));
                             tokio::time::sleep(Duration::from_secs_f32(0.5)).await;
                         }
                         label
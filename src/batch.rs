@@ -0,0 +1,417 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::task::Task;
+
+/// Unique identifier of a [`Batch`] pushed to the manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BatchId(u64);
+
+impl BatchId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Position of a task in its batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BatchSlot {
+    pub(crate) id: BatchId,
+    pub(crate) index: usize,
+}
+
+/// Several tasks that are pushed as one named group, e.g. "Import 240 files".
+///
+/// Tasks are executed by the collection's executor as usual but their results are not
+/// passed to the collection's handler. Instead the [`on_complete`](Self::on_complete)
+/// callback is called once with all results when every task is finished.
+///
+/// ```rust
+/// # use egui_task_manager::*;
+/// let batch = Batch::new("Import 3 files")
+///     .with_tasks((1..=3).map(|i| {
+///         Task::new(format!("File {i}"), Caller::standard(async move { i }))
+///     }))
+///     .on_complete(|results: Vec<u32>| println!("Imported {} files", results.len()));
+///
+/// assert_eq!(batch.len(), 3);
+/// ```
+pub struct Batch<T> {
+    name: String,
+    tasks: Vec<Task<T>>,
+    on_complete: Option<Box<dyn FnOnce(Vec<T>)>>,
+}
+
+impl<T: Send + 'static> Batch<T> {
+    /// Creates an empty batch with the provided name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            tasks: Vec::new(),
+            on_complete: None,
+        }
+    }
+
+    /// Adds a task to the batch.
+    pub fn with_task(mut self, task: Task<T>) -> Self {
+        self.push(task);
+        self
+    }
+
+    /// Adds several tasks to the batch.
+    pub fn with_tasks(mut self, tasks: impl IntoIterator<Item = Task<T>>) -> Self {
+        self.tasks.extend(tasks);
+        self
+    }
+
+    /// Adds a task to the batch.
+    pub fn push(&mut self, task: Task<T>) {
+        self.tasks.push(task);
+    }
+
+    /// Sets the callback that is called once all tasks are finished.
+    ///
+    /// Results are in the same order as tasks were added. Results of the cancelled
+    /// and panicked tasks are missing. The callback is not called if the whole batch
+    /// is cancelled. Continuations of the batch's tasks (see [`Task::then`]) are dropped
+    /// so every result reaches the callback.
+    pub fn on_complete(mut self, on_complete: impl FnOnce(Vec<T>) + 'static) -> Self {
+        self.on_complete = Some(Box::new(on_complete));
+        self
+    }
+
+    /// Batch's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of tasks in the batch.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Checks if the batch has no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Splits the batch into the data kept by the collection, the storage of its
+    /// results and the tasks that must be pushed to the executor.
+    pub(crate) fn into_parts(self) -> (BatchData, BatchResults<T>, Vec<Task<T>>) {
+        let id = BatchId::next();
+        let total = self.tasks.len();
+
+        let tasks = self
+            .tasks
+            .into_iter()
+            .enumerate()
+            .map(|(index, task)| task.with_batch(BatchSlot { id, index }))
+            .collect();
+
+        let data = BatchData {
+            id,
+            name: self.name,
+            slots: (0..total).map(|_| SlotState::Pending).collect(),
+            cancelled: AtomicBool::new(false),
+        };
+
+        let results = BatchResults {
+            id,
+            values: (0..total).map(|_| None).collect(),
+            on_complete: self.on_complete,
+        };
+
+        (data, results, tasks)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotState {
+    Pending,
+    Completed,
+    Cancelled,
+}
+
+/// Results of the batch's tasks.
+pub(crate) struct BatchResults<T> {
+    id: BatchId,
+    values: Vec<Option<T>>,
+    on_complete: Option<Box<dyn FnOnce(Vec<T>)>>,
+}
+
+impl<T> BatchResults<T> {
+    pub(crate) fn id(&self) -> BatchId {
+        self.id
+    }
+
+    pub(crate) fn insert(&mut self, index: usize, value: T) {
+        if let Some(slot) = self.values.get_mut(index) {
+            *slot = Some(value);
+        }
+    }
+
+    /// Calls the callback with the received results.
+    pub(crate) fn complete(self) {
+        if let Some(on_complete) = self.on_complete {
+            (on_complete)(self.values.into_iter().flatten().collect())
+        }
+    }
+}
+
+/// The data of a [`Batch`] that is being executed.
+pub struct BatchData {
+    id: BatchId,
+    name: String,
+    slots: Vec<SlotState>,
+    cancelled: AtomicBool,
+}
+
+impl BatchData {
+    /// Batch's id.
+    pub fn id(&self) -> BatchId {
+        self.id
+    }
+
+    /// Batch's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Total number of tasks in the batch.
+    pub fn total(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Number of tasks that completed successfully.
+    pub fn completed(&self) -> usize {
+        self.count(SlotState::Completed)
+    }
+
+    /// Number of tasks that were cancelled.
+    pub fn cancelled_tasks(&self) -> usize {
+        self.count(SlotState::Cancelled)
+    }
+
+    /// Checks if the whole batch was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Checks if all tasks of the batch are finished.
+    pub fn is_finished(&self) -> bool {
+        !self.slots.contains(&SlotState::Pending)
+    }
+
+    /// Marks the batch as cancelled. Its tasks that have not been started yet won't be started.
+    ///
+    /// Running tasks must be cancelled separately,
+    /// see [`CollectionData::cancel_batch`](crate::CollectionData::cancel_batch).
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn count(&self, state: SlotState) -> usize {
+        self.slots.iter().filter(|slot| **slot == state).count()
+    }
+
+    pub(crate) fn mark_completed(&mut self, index: usize) {
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = SlotState::Completed;
+        }
+    }
+
+    pub(crate) fn mark_cancelled(&mut self, index: usize) {
+        if let Some(slot @ SlotState::Pending) = self.slots.get_mut(index) {
+            *slot = SlotState::Cancelled;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use crate::{testing::poll_until, *};
+
+    struct Imports;
+
+    impl<'c> TasksCollection<'c> for Imports {
+        type Context = ();
+        type Target = u32;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Imports"
+        }
+
+        fn handle(_context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|_| ())
+        }
+    }
+
+    struct Queue;
+
+    impl<'c> TasksCollection<'c> for Queue {
+        type Context = ();
+        type Target = u32;
+        type Executor = executors::Linear;
+
+        fn name() -> &'static str {
+            "Queue"
+        }
+
+        fn handle(_context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|_| ())
+        }
+    }
+
+    struct Thumbnails;
+
+    impl<'c> TasksCollection<'c> for Thumbnails {
+        type Context = &'c mut Vec<u32>;
+        type Target = u32;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Thumbnails"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|value| context.push(value))
+        }
+    }
+
+    #[test]
+    fn on_complete_receives_the_results_in_order() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Imports>();
+
+        let imported = Rc::new(RefCell::new(None));
+        let cloned = imported.clone();
+        let batch = Batch::new("Import 3 files")
+            .with_tasks(
+                (1..=3).map(|i| Task::new(format!("File {i}"), Caller::standard(async move { i }))),
+            )
+            .on_complete(move |results| *cloned.borrow_mut() = Some(results));
+        manager.push_batch::<Imports>(batch);
+
+        poll_until(|| {
+            manager.process(Contexts::new());
+            imported.borrow().is_some()
+        });
+        assert_eq!(*imported.borrow(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn results_of_cancelled_and_panicked_tasks_are_missing() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Imports>();
+
+        let imported = Rc::new(RefCell::new(None));
+        let cloned = imported.clone();
+        let batch = Batch::new("Import 3 files")
+            .with_task(Task::new("File 1", Caller::standard(async { 1 })))
+            .with_task(Task::new(
+                "File 2",
+                Caller::standard(async { panic!("Corrupted file") }),
+            ))
+            .with_task(Task::new(
+                "File 3",
+                Caller::standard(std::future::pending()),
+            ))
+            .on_complete(move |results| *cloned.borrow_mut() = Some(results));
+        manager.push_batch::<Imports>(batch);
+
+        manager.process(Contexts::new());
+        let collection = manager.get_collection::<Imports>();
+        for task in collection
+            .tasks()
+            .iter()
+            .filter(|task| task.name() == "File 3")
+        {
+            task.cancel();
+        }
+
+        poll_until(|| {
+            manager.process(Contexts::new());
+            imported.borrow().is_some()
+        });
+        assert_eq!(*imported.borrow(), Some(vec![1]));
+
+        let stats = manager.get_collection::<Imports>().stats();
+        assert_eq!(
+            (stats.completed(), stats.failed(), stats.cancelled()),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn cancelled_batch_is_not_completed() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Queue>();
+
+        let completed = Rc::new(Cell::new(false));
+        let cloned = completed.clone();
+        let batch = Batch::new("Import 3 files")
+            .with_tasks((1..=3).map(|i| {
+                Task::new(
+                    format!("File {i}"),
+                    Caller::standard(std::future::pending()),
+                )
+            }))
+            .on_complete(move |_| cloned.set(true));
+        let id = manager.push_batch::<Queue>(batch);
+
+        manager.process(Contexts::new());
+        assert_eq!(manager.get_collection::<Queue>().tasks().len(), 1);
+        manager.get_collection::<Queue>().cancel_batch(id);
+
+        poll_until(|| {
+            manager.process(Contexts::new());
+            manager.get_collection::<Queue>().batches().is_empty()
+        });
+        assert_eq!(manager.get_collection::<Queue>().stats().cancelled(), 3);
+        assert!(!completed.get());
+    }
+
+    #[test]
+    fn continuations_of_batch_tasks_are_dropped() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager
+            .register_collection::<Imports>()
+            .register_collection::<Thumbnails>();
+
+        let imported = Rc::new(RefCell::new(None));
+        let cloned = imported.clone();
+        let batch = Batch::new("Import 2 files")
+            .with_task(Task::new("File 1", Caller::standard(async { 1 })))
+            .with_task(
+                Task::new("File 2", Caller::standard(async { 2 })).then::<Thumbnails>(|i| {
+                    Task::new("Thumbnail", Caller::standard(async move { i * 10 }))
+                }),
+            )
+            .on_complete(move |results| *cloned.borrow_mut() = Some(results));
+        manager.push_batch::<Imports>(batch);
+
+        let mut thumbnails = Vec::new();
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Thumbnails>(&mut thumbnails));
+            imported.borrow().is_some()
+        });
+
+        assert_eq!(*imported.borrow(), Some(vec![1, 2]));
+        assert!(thumbnails.is_empty());
+        assert!(manager.get_collection::<Thumbnails>().tasks().is_empty());
+    }
+}
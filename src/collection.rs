@@ -1,11 +1,31 @@
-use std::any::Any;
+use std::{
+    any::{type_name, Any},
+    borrow::Cow,
+    collections::VecDeque,
+};
+
+use std::sync::mpsc::Sender;
 
 use crate::{
+    batch::{Batch, BatchData, BatchId, BatchResults},
     channel::Channel,
-    execution::{AnyHandler, Handler, TaskExecutor},
-    task::{AnyTask, TaskData},
+    error::TaskManagerError,
+    events::{SharedSubscribers, Subscribers, TaskEvent, TaskEventKind},
+    execution::{Handler, TaskExecutor, TaskProgress},
+    history::{TaskHistory, TaskRecord, TaskStatus},
+    manager::Spawn,
+    spawning::PauseGate,
+    stats::CollectionStats,
+    task::{AnyTask, Task, TaskData, TaskId, TaskOutcome, TaskOutput},
 };
 
+#[cfg(feature = "tracing")]
+use crate::instrument;
+#[cfg(feature = "serde")]
+use crate::persistence::TaskSpec;
+#[cfg(feature = "metrics")]
+use crate::telemetry;
+
 /// Describes the collection of tasks.
 ///
 /// ```rust
@@ -42,36 +62,255 @@ pub trait TasksCollection<'c> {
     /// Collection's name that will be displayed.
     fn name() -> &'static str;
 
+    /// Key that identifies the collection, e.g. in [`TaskSpec`](crate::TaskSpec)s
+    /// and metrics. Defaults to the [`name`](TasksCollection::name).
+    ///
+    /// It must be unique among the collections and stay the same between launches.
+    fn key() -> &'static str {
+        Self::name()
+    }
+
+    /// Determines the position of the collection among others.
+    /// Collections with lower keys go first.
+    ///
+    /// See [`TaskManager::iter_collections`](crate::TaskManager::iter_collections).
+    fn sort_key() -> i32 {
+        0
+    }
+
+    /// Group the collection belongs to.
+    ///
+    /// See [`TaskManager::iter_groups`](crate::TaskManager::iter_groups).
+    fn group() -> Option<&'static str> {
+        None
+    }
+
+    /// Maximum number of tasks the collection can start in one round when
+    /// the manager uses [`Fairness::Weighted`](crate::Fairness::Weighted).
+    fn weight() -> u32 {
+        1
+    }
+
     /// Handle that handles task's results. It can capture the context provided
     /// by the [`Context`](TasksCollection::Context).
     fn handle(context: Self::Context) -> Handler<'c, Self::Target>;
 }
 
+/// Determines what happens with the running tasks when the collection is paused.
+///
+/// In both cases no new tasks are started until the collection is resumed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PausePolicy {
+    /// Running tasks continue their execution.
+    #[default]
+    Continue,
+    /// Running tasks are suspended at their next `.await` point
+    /// until the collection is resumed.
+    Suspend,
+}
+
+/// Handles the results of a collection using a handler of the right type.
+pub(crate) type ResultsHandler<'h> = Box<dyn FnOnce(&mut CollectionData) + 'h>;
+
+/// The part of the collection that depends on the type of its tasks' results.
+///
+/// It lets [`CollectionData`] keep a typed channel without being generic.
+trait ErasedCollection {
+    /// Receives the results of the finished tasks.
+    fn receive(&mut self, batches: &mut [BatchData]);
+
+    /// Handles the received results using the registered handler if there's one.
+    fn handle_registered(&mut self);
+
+    /// Calls the batch's callback unless the batch was cancelled.
+    fn complete_batch(&mut self, batch: BatchData);
+
+    /// Drops the results of all batches.
+    fn clear_batches(&mut self);
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct TypedCollection<T> {
+    channel: Channel<TaskOutput<T>>,
+    unhandled: VecDeque<(T, TaskOutcome)>,
+    handler: Option<Handler<'static, T>>,
+    batches: Vec<BatchResults<T>>,
+}
+
+impl<T: 'static> ErasedCollection for TypedCollection<T> {
+    fn receive(&mut self, batches: &mut [BatchData]) {
+        for output in self.channel.receiver().try_iter() {
+            let Some(slot) = output.batch else {
+                if let Some(value) = output.value {
+                    self.unhandled.push_back((value, output.outcome));
+                }
+                continue;
+            };
+
+            if let Some(batch) = batches.iter_mut().find(|batch| batch.id() == slot.id) {
+                batch.mark_completed(slot.index);
+            }
+
+            let results = self.batches.iter_mut().find(|batch| batch.id() == slot.id);
+            if let (Some(results), Some(value)) = (results, output.value) {
+                results.insert(slot.index, value);
+            }
+        }
+    }
+
+    fn handle_registered(&mut self) {
+        if let Some(handle) = &mut self.handler {
+            for (value, outcome) in self.unhandled.drain(..) {
+                handle.apply(value, outcome)
+            }
+        }
+    }
+
+    fn complete_batch(&mut self, batch: BatchData) {
+        let Some(index) = self
+            .batches
+            .iter()
+            .position(|results| results.id() == batch.id())
+        else {
+            return;
+        };
+
+        let results = self.batches.remove(index);
+        if !batch.is_cancelled() {
+            results.complete()
+        }
+    }
+
+    fn clear_batches(&mut self) {
+        self.batches.clear()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Collection holds the tasks in the queue and the data of currently executing ones.
 ///
 /// It uses [`TaskExecutor`](crate::TaskExecutor) to determine when a new task should
 /// start it's execution.
 pub struct CollectionData {
-    name: &'static str,
-    channel: Channel<Box<dyn Any + Send>>,
+    name: Cow<'static, str>,
+    key: Cow<'static, str>,
+    target: &'static str,
+    sort_key: i32,
+    group: Option<Cow<'static, str>>,
+    weight: u32,
+    results: Box<dyn ErasedCollection>,
     tasks: Vec<TaskData>,
+    batches: Vec<BatchData>,
+    spawns: Option<Sender<Spawn>>,
+    subscribers: Subscribers,
+    history: TaskHistory,
+    records: Option<Sender<TaskRecord>>,
+    stats: CollectionStats,
+    show_stats: bool,
     executor: Box<dyn TaskExecutor>,
+    paused: Option<PausePolicy>,
+    gate: PauseGate,
 }
 
 impl CollectionData {
     #[cfg(feature = "egui")]
     /// Draws a simple ui.
     pub fn ui(&self, ui: &mut egui::Ui) {
-        ui.collapsing(self.name, |ui| {
-            for task in &self.tasks {
+        ui.collapsing(self.name.as_ref(), |ui| {
+            if self.is_paused() {
+                ui.weak("Paused");
+            }
+
+            if self.show_stats {
+                egui::CollapsingHeader::new("Statistics")
+                    .id_source(("collection_stats", self.key.as_ref()))
+                    .show(ui, |ui| self.stats.ui(ui));
+            }
+
+            for task in self.tasks.iter().filter(|task| task.batch_id().is_none()) {
                 ui.group(|ui| task.ui(ui));
             }
+
+            for batch in &self.batches {
+                ui.group(|ui| self.batch_ui(ui, batch));
+            }
         });
     }
 
+    #[cfg(feature = "egui")]
+    fn batch_ui(&self, ui: &mut egui::Ui, batch: &BatchData) {
+        ui.label(batch.name());
+
+        let finished = batch.completed() + batch.cancelled_tasks();
+        ui.add(
+            egui::ProgressBar::new(finished as f32 / batch.total().max(1) as f32).text(format!(
+                "{}/{}",
+                batch.completed(),
+                batch.total()
+            )),
+        );
+
+        let running = self
+            .tasks
+            .iter()
+            .filter(|task| task.batch_id() == Some(batch.id()))
+            .collect::<Vec<_>>();
+
+        egui::CollapsingHeader::new(format!("Running tasks ({})", running.len()))
+            .id_source(("batch_tasks", batch.id()))
+            .show(ui, |ui| {
+                for task in running {
+                    ui.group(|ui| task.ui(ui));
+                }
+            });
+
+        if ui.button("Cancel all").clicked() {
+            self.cancel_batch(batch.id());
+        }
+    }
+
     /// Collection name.
     pub fn name(&self) -> &str {
-        self.name
+        &self.name
+    }
+
+    /// Collection's sort key. See [`TasksCollection::sort_key`].
+    pub fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+
+    /// Sets collection's sort key.
+    pub fn set_sort_key(&mut self, sort_key: i32) {
+        self.sort_key = sort_key;
+    }
+
+    /// Group the collection belongs to. See [`TasksCollection::group`].
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Sets the group of the collection.
+    pub fn set_group(&mut self, group: impl Into<Cow<'static, str>>) {
+        self.group = Some(group.into());
+    }
+
+    /// Removes the collection from its group.
+    pub fn clear_group(&mut self) {
+        self.group = None;
+    }
+
+    /// Collection's weight. See [`TasksCollection::weight`].
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// Sets collection's weight.
+    pub fn set_weight(&mut self, weight: u32) {
+        self.weight = weight;
     }
 
     /// Returns a slice of the currently running tasks.
@@ -94,61 +333,521 @@ impl CollectionData {
         C: TasksCollection<'c>,
         C::Executor: 'static,
     {
+        let mut collection = Self::new::<C::Target>(C::name(), Box::<C::Executor>::default());
+        collection.key = Cow::Borrowed(C::key());
+        collection.sort_key = C::sort_key();
+        collection.group = C::group().map(Cow::Borrowed);
+        collection.weight = C::weight();
+        collection
+    }
+
+    pub(super) fn new<T: 'static>(
+        name: impl Into<Cow<'static, str>>,
+        executor: Box<dyn TaskExecutor>,
+    ) -> Self {
+        let name = name.into();
         Self {
-            name: C::name(),
-            channel: Channel::new(),
+            key: name.clone(),
+            name,
+            target: type_name::<T>(),
+            sort_key: 0,
+            group: None,
+            weight: 1,
+            results: Box::new(TypedCollection::<T> {
+                channel: Channel::new(),
+                unhandled: VecDeque::new(),
+                handler: None,
+                batches: Vec::new(),
+            }),
             tasks: Vec::new(),
-            executor: Box::<C::Executor>::default(),
+            batches: Vec::new(),
+            spawns: None,
+            subscribers: Subscribers::default(),
+            history: TaskHistory::default(),
+            records: None,
+            stats: CollectionStats::default(),
+            show_stats: false,
+            executor,
+            paused: None,
+            gate: PauseGate::default(),
+        }
+    }
+
+    /// Key that identifies the collection in the manager: the key of a dynamic collection
+    /// or [`TasksCollection::key`].
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(super) fn set_key(&mut self, key: impl Into<Cow<'static, str>>) {
+        self.key = key.into();
+    }
+
+    /// Type name of the value that tasks in this collection return.
+    pub fn target_name(&self) -> &'static str {
+        self.target
+    }
+
+    /// Returns the typed part of the collection or an error if its tasks don't return `T`.
+    fn typed<T: 'static>(&mut self) -> Result<&mut TypedCollection<T>, TaskManagerError> {
+        let (name, target) = (&self.name, self.target);
+
+        self.results
+            .as_any_mut()
+            .downcast_mut::<TypedCollection<T>>()
+            .ok_or_else(|| TaskManagerError::TargetMismatch {
+                collection: name.to_string(),
+                expected: target,
+                found: type_name::<T>(),
+            })
+    }
+
+    /// Sets the sender that receives tasks created by continuations.
+    pub(crate) fn set_spawns(&mut self, spawns: Sender<Spawn>) {
+        self.spawns = Some(spawns);
+    }
+
+    /// Sets the sender that receives the records of finished tasks.
+    pub(crate) fn set_records(&mut self, records: Sender<TaskRecord>) {
+        self.records = Some(records);
+    }
+
+    /// Counters and timings of the collection's tasks.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// egui_task_manager::setup!();
+    ///
+    /// let mut manager = TaskManager::new();
+    /// manager.add_dynamic_collection("files", "Files", executors::Linear::default(), Handler::new(|_: ()| ()));
+    /// manager.push_dynamic_task("files", Task::new("Copy", Caller::standard(async {}))).unwrap();
+    ///
+    /// let stats = manager.get_dynamic_collection("files").unwrap().stats();
+    /// println!("{} queued, {} completed", stats.queue_depth(), stats.completed());
+    /// ```
+    pub fn stats(&self) -> &CollectionStats {
+        &self.stats
+    }
+
+    /// Whether the [stats](Self::stats) are shown in the collection's [`ui`](Self::ui).
+    pub fn set_show_stats(&mut self, show: bool) {
+        self.show_stats = show;
+    }
+
+    /// History of the finished tasks of this collection.
+    pub fn history(&self) -> &TaskHistory {
+        &self.history
+    }
+
+    /// Mutable reference to the history of the finished tasks of this collection.
+    pub fn history_mut(&mut self) -> &mut TaskHistory {
+        &mut self.history
+    }
+
+    /// Sets the listeners that receive the events of all collections.
+    pub(crate) fn set_shared_subscribers(&mut self, shared: SharedSubscribers) {
+        self.subscribers.set_shared(shared);
+    }
+
+    /// Adds a listener that is called on every [`TaskEvent`] of this collection.
+    ///
+    /// Listeners are called while the collection is being processed.
+    pub fn subscribe(&mut self, listener: impl FnMut(&TaskEvent<'_>) + 'static) {
+        self.subscribers.push(listener);
+    }
+
+    fn emit(&mut self, id: TaskId, task_name: &str, kind: TaskEventKind<'_>) {
+        self.subscribers.emit(TaskEvent {
+            collection: &self.name,
+            id,
+            name: task_name,
+            kind,
+        });
+    }
+
+    /// Sets the handler that is used when no other handler is provided.
+    pub(crate) fn set_handler<T: 'static>(&mut self, handler: Handler<'static, T>) {
+        if let Ok(results) = self.typed::<T>() {
+            results.handler = Some(handler);
         }
     }
 
-    fn execute(&mut self, task: AnyTask) {
-        let sender = self.channel.sender();
-        let task_data = task.execute(sender);
+    /// Executes the task unless its batch was cancelled.
+    ///
+    /// Returns `true` if the task was started.
+    fn execute(&mut self, task: AnyTask) -> bool {
+        if let Some(slot) = task.batch_slot() {
+            let batch = self.batches.iter_mut().find(|batch| batch.id() == slot.id);
+            if let Some(batch) = batch.filter(|batch| batch.is_cancelled()) {
+                batch.mark_cancelled(slot.index);
+                self.stats.record_dequeued();
+                #[cfg(feature = "tracing")]
+                instrument::trace(task.span(), &TaskEventKind::TaskCancelled, None);
+                #[cfg(feature = "metrics")]
+                {
+                    telemetry::finished(&self.metric_labels(), TaskStatus::Cancelled, None);
+                    self.update_task_counts();
+                }
+                self.emit(task.id(), task.name(), TaskEventKind::TaskCancelled);
+                return false;
+            }
+        }
+
+        self.stats.record_started(task.waited());
+        #[cfg(feature = "tracing")]
+        instrument::trace(task.span(), &TaskEventKind::TaskStarted, None);
+        #[cfg(feature = "metrics")]
+        telemetry::started(&self.metric_labels(), task.waited());
+        self.emit(task.id(), task.name(), TaskEventKind::TaskStarted);
+        let task_data = task.execute(&self.gate);
         self.push_task_data(task_data);
+        #[cfg(feature = "metrics")]
+        self.update_task_counts();
+        true
+    }
+
+    #[cfg(feature = "metrics")]
+    /// Publishes the number of running and queued tasks.
+    fn update_task_counts(&self) {
+        telemetry::set_task_counts(
+            &self.metric_labels(),
+            self.tasks.len(),
+            self.stats.queue_depth(),
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metric_labels(&self) -> Vec<metrics::Label> {
+        telemetry::labels(&self.name, &self.key)
     }
 
     fn push_task_data(&mut self, task_data: TaskData) {
         self.tasks.push(task_data)
     }
 
-    pub(crate) fn push_task(&mut self, task: AnyTask) {
+    /// Pushes the task to the executor or returns an error if it doesn't return
+    /// the collection's type.
+    pub(crate) fn push_task<T: Send + 'static>(
+        &mut self,
+        task: Task<T>,
+    ) -> Result<(), TaskManagerError> {
+        let spawns = self.spawns.clone();
+        let sender = self.typed::<T>()?.channel.sender();
+
+        self.push_any_task(task.bind(sender, spawns));
+        Ok(())
+    }
+
+    /// Pushes the task that already knows where to send its result.
+    pub(crate) fn push_any_task(&mut self, mut task: AnyTask) {
+        task.mark_queued();
+        self.stats.record_queued();
+        #[cfg(feature = "tracing")]
+        {
+            task.set_span(&self.name);
+            instrument::trace(task.span(), &TaskEventKind::TaskQueued, None);
+        }
+        self.emit(task.id(), task.name(), TaskEventKind::TaskQueued);
         self.executor.push(task);
+        #[cfg(feature = "metrics")]
+        self.update_task_counts();
+    }
+
+    pub(crate) fn push_batch<T: Send + 'static>(
+        &mut self,
+        batch: Batch<T>,
+    ) -> Result<BatchId, TaskManagerError> {
+        let spawns = self.spawns.clone();
+        let typed = self.typed::<T>()?;
+        let sender = typed.channel.sender();
+
+        let (data, results, tasks) = batch.into_parts();
+        let id = data.id();
+
+        typed.batches.push(results);
+        self.batches.push(data);
+        for task in tasks {
+            self.push_any_task(task.bind(sender.clone(), spawns.clone()));
+        }
+        Ok(id)
+    }
+
+    /// Batches that are being executed.
+    pub fn batches(&self) -> &[BatchData] {
+        &self.batches
+    }
+
+    /// Cancels the batch. Its running tasks are cancelled and queued ones won't be started.
+    pub fn cancel_batch(&self, id: BatchId) {
+        let Some(batch) = self.batches.iter().find(|batch| batch.id() == id) else {
+            return;
+        };
+
+        batch.cancel();
+        for task in self.tasks.iter().filter(|task| task.batch_id() == Some(id)) {
+            task.cancel();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    /// Specs of the running tasks followed by the specs of the queued ones.
+    ///
+    /// Tasks that were not created from a spec are skipped.
+    pub fn task_specs(&self) -> impl Iterator<Item = &TaskSpec> {
+        let running = self.tasks.iter().filter_map(TaskData::spec);
+        let queued = self.executor.iter_tasks().filter_map(|task| task.spec());
+        running.chain(queued)
+    }
+
+    /// Cancels all tasks and records the cancelled running ones, used when the collection
+    /// is removed from the manager and won't be processed anymore.
+    pub(crate) fn shut_down(&mut self) {
+        self.cancel_all();
+        self.handle_deletion();
+        #[cfg(feature = "metrics")]
+        telemetry::set_task_counts(&self.metric_labels(), 0, 0);
+    }
+
+    /// Cancels all running tasks and removes the ones that are waiting to be executed.
+    pub fn cancel_all(&mut self) {
+        for _ in self.executor.iter_tasks() {
+            self.stats.record_dequeued();
+            #[cfg(feature = "metrics")]
+            telemetry::finished(&self.metric_labels(), TaskStatus::Cancelled, None);
+        }
+        #[cfg(feature = "tracing")]
+        for task in self.executor.iter_tasks() {
+            instrument::trace(task.span(), &TaskEventKind::TaskCancelled, None);
+        }
+
+        if !self.subscribers.is_empty() {
+            let queued = self
+                .executor
+                .iter_tasks()
+                .map(|task| (task.id(), task.name().to_owned()))
+                .collect::<Vec<_>>();
+            for (id, name) in queued {
+                self.emit(id, &name, TaskEventKind::TaskCancelled);
+            }
+        }
+
+        self.executor.clear();
+        for task in &self.tasks {
+            task.cancel();
+        }
+        #[cfg(feature = "metrics")]
+        self.update_task_counts();
+        self.batches.clear();
+        self.results.clear_batches();
+    }
+
+    /// Pauses the collection. New tasks won't be started until [`resume`](Self::resume)
+    /// is called. Running tasks are handled according to the `policy`.
+    pub fn pause(&mut self, policy: PausePolicy) {
+        self.paused = Some(policy);
+        self.gate.set_paused(policy == PausePolicy::Suspend);
+    }
+
+    /// Resumes the collection and its suspended tasks.
+    pub fn resume(&mut self) {
+        self.paused = None;
+        self.gate.set_paused(false);
+    }
+
+    /// Checks if the collection is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Policy the collection was paused with.
+    pub fn pause_policy(&self) -> Option<PausePolicy> {
+        self.paused
     }
 
     /// Сalls all handle-methods, in this order:
     /// - [`handle_execution`](Self::handle_execution)
     /// - [`handle_progress`](Self::handle_progress)
+    /// - [`handle_logs`](Self::handle_logs)
+    /// - [`handle_questions`](Self::handle_questions)
     /// - [`handle_results`](Self::handle_results)
     /// - [`handle_deletion`](Self::handle_deletion)
-    pub fn handle_all(&mut self, result_handle: AnyHandler<'_>) {
+    /// - [`handle_batches`](Self::handle_batches)
+    ///
+    /// Returns an error if the tasks of this collection don't return `T`.
+    pub fn handle_all<T: 'static>(
+        &mut self,
+        result_handle: Handler<'_, T>,
+    ) -> Result<(), TaskManagerError> {
+        self.typed::<T>()?;
         self.handle_execution();
+        self.process_running(Some(Self::results_handler(result_handle)));
+        Ok(())
+    }
+
+    /// Same as [`handle_all`](Self::handle_all) but the results are handled by the
+    /// handler registered with the collection. See
+    /// [`TaskManager::register_collection_with`](crate::TaskManager::register_collection_with).
+    ///
+    /// If there's no such handler the results stay in the queue until a handle is provided.
+    pub fn process(&mut self) {
+        self.handle_execution();
+        self.process_running(None);
+    }
+
+    /// Handles everything except the execution.
+    pub(crate) fn process_running(&mut self, result_handle: Option<ResultsHandler<'_>>) {
         self.handle_progress();
-        self.handle_results(result_handle);
+        self.handle_logs();
+        self.handle_questions();
+        match result_handle {
+            Some(handle) => (handle)(self),
+            None => self.handle_registered_results(),
+        }
         self.handle_deletion();
+        self.handle_batches();
+    }
+
+    /// Wraps the handler so it can be passed to [`process_running`](Self::process_running).
+    pub(crate) fn results_handler<T: 'static>(handle: Handler<'_, T>) -> ResultsHandler<'_> {
+        Box::new(move |collection| {
+            let _ = collection.handle_results(handle);
+        })
     }
 
-    /// Handles tasks execution results using provided handle.
-    pub fn handle_results(&mut self, mut handle: AnyHandler<'_>) {
-        if let Ok(value) = self.channel.receiver().try_recv() {
-            handle.apply(value)
+    /// Handles all received tasks execution results using provided handle.
+    ///
+    /// Results of the tasks that belong to a batch are stored in the batch.
+    /// Returns an error if the tasks of this collection don't return `T`.
+    pub fn handle_results<T: 'static>(
+        &mut self,
+        mut handle: Handler<'_, T>,
+    ) -> Result<(), TaskManagerError> {
+        self.receive_results();
+        for (value, outcome) in self.typed::<T>()?.unhandled.drain(..) {
+            handle.apply(value, outcome)
         }
+        Ok(())
+    }
+
+    fn handle_registered_results(&mut self) {
+        self.receive_results();
+        self.results.handle_registered();
+    }
+
+    fn receive_results(&mut self) {
+        self.results.receive(&mut self.batches);
     }
 
     /// Handles tasks deletion.
+    ///
+    /// Finished tasks are recorded in the [history](Self::history).
     pub fn handle_deletion(&mut self) {
-        self.tasks.retain(|task| !task.is_finished())
+        #[cfg(feature = "metrics")]
+        let labels = self.metric_labels();
+        let (collection, batches, subscribers) =
+            (&self.name, &mut self.batches, &mut self.subscribers);
+        let (history, records, stats) = (&mut self.history, &self.records, &mut self.stats);
+
+        self.tasks.retain(|task| {
+            if !task.is_finished() {
+                return true;
+            }
+
+            let status = TaskStatus::of(task);
+            let kind = match status {
+                TaskStatus::Completed => {
+                    stats.record_completed(task.duration());
+                    TaskEventKind::TaskCompleted {
+                        duration: task.duration(),
+                    }
+                }
+                TaskStatus::Cancelled => {
+                    stats.record_cancelled();
+                    TaskEventKind::TaskCancelled
+                }
+                TaskStatus::Failed => {
+                    stats.record_failed();
+                    TaskEventKind::TaskFailed
+                }
+            };
+
+            let record = TaskRecord::new(collection, task);
+            if let Some(records) = records {
+                let _ = records.send(record.clone());
+            }
+            history.push(record);
+
+            let unfinished = status != TaskStatus::Completed;
+            if let Some(slot) = task.batch_slot().filter(|_| unfinished) {
+                if let Some(batch) = batches.iter_mut().find(|batch| batch.id() == slot.id) {
+                    batch.mark_cancelled(slot.index);
+                }
+            }
+
+            #[cfg(feature = "tracing")]
+            instrument::trace(task.span(), &kind, task.error());
+            #[cfg(feature = "metrics")]
+            telemetry::finished(&labels, status, Some(task.duration()));
+            subscribers.emit(TaskEvent {
+                collection,
+                id: task.id(),
+                name: task.name(),
+                kind,
+            });
+            false
+        });
+
+        #[cfg(feature = "metrics")]
+        self.update_task_counts();
+    }
+
+    /// Handles finished batches by calling their callbacks.
+    pub fn handle_batches(&mut self) {
+        let (finished, batches) = std::mem::take(&mut self.batches)
+            .into_iter()
+            .partition(BatchData::is_finished);
+        self.batches = batches;
+
+        for batch in finished {
+            self.results.complete_batch(batch);
+        }
     }
 
     /// Handles tasks progress.
     pub fn handle_progress(&mut self) {
-        for progress in self.tasks.iter_mut().filter_map(|task| task.progress_mut()) {
-            if let Ok(data) = progress.receiver().try_recv() {
-                data.apply(progress.current_mut())
+        let (collection, subscribers) = (&self.name, &mut self.subscribers);
+
+        for task in &mut self.tasks {
+            let updated = task.progress_mut().is_some_and(TaskProgress::apply_updates);
+            #[cfg(feature = "tracing")]
+            if updated {
+                task.trace_progress();
+            }
+            if let Some(progress) = task.progress().filter(|_| updated) {
+                subscribers.emit(TaskEvent {
+                    collection,
+                    id: task.id(),
+                    name: task.name(),
+                    kind: TaskEventKind::Progress(&progress.combined_state()),
+                });
             }
         }
     }
 
+    /// Handles tasks logs.
+    pub fn handle_logs(&mut self) {
+        for log in self.tasks.iter_mut().filter_map(TaskData::log_mut) {
+            log.receive()
+        }
+    }
+
+    /// Handles questions asked by the tasks.
+    pub fn handle_questions(&mut self) {
+        for task in &mut self.tasks {
+            task.questions_mut().receive()
+        }
+    }
+
     /// Handles tasks execution.
     ///
     /// More specifically it calls [`TasksExecutor::poll`](crate::TaskExecutor) method
@@ -156,10 +855,67 @@ impl CollectionData {
     /// as long as it returns [`ExecutionPoll::Ready`](crate::ExecutionPoll) which means
     /// that there's still tasks to execute. If [`ExecutionPoll::Pending`](crate::ExecutionPoll)
     /// is returned it will stop the polling.
+    ///
+    /// The executor is not polled while the collection is paused.
     pub fn handle_execution(&mut self) {
+        self.handle_execution_limited(usize::MAX);
+    }
+
+    /// Same as [`handle_execution`](Self::handle_execution) but starts at most `limit` tasks.
+    ///
+    /// Returns the number of started tasks.
+    pub fn handle_execution_limited(&mut self, limit: usize) -> usize {
+        if self.is_paused() {
+            return 0;
+        }
+
         use crate::execution::ExecutionPoll as E;
-        while let E::Ready(task) = self.executor.poll(&self.tasks) {
-            self.execute(task)
+        let mut started = 0;
+        while started < limit {
+            let E::Ready(task) = self.executor.poll(&self.tasks) else {
+                break;
+            };
+            if self.execute(task) {
+                started += 1;
+            }
         }
+        started
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::poll_until, *};
+
+    fn stats(manager: &TaskManager) -> &CollectionStats {
+        manager.get_dynamic_collection("files").unwrap().stats()
+    }
+
+    #[test]
+    fn stats_count_the_tasks() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.add_dynamic_collection(
+            "files",
+            "Files",
+            executors::Linear::default(),
+            Handler::new(|_: ()| ()),
+        );
+        for _ in 0..3 {
+            let task = Task::new("Copy", Caller::standard(async {}));
+            manager.push_dynamic_task("files", task).unwrap();
+        }
+        assert_eq!(stats(&manager).queue_depth(), 3);
+
+        poll_until(|| {
+            manager.process(Contexts::new());
+            stats(&manager).completed() == 3
+        });
+
+        let stats = stats(&manager);
+        assert_eq!(stats.queued(), 3);
+        assert_eq!(stats.queue_depth(), 0);
+        assert!(stats.p95_duration().is_some());
     }
 }
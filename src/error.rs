@@ -0,0 +1,186 @@
+use std::{error::Error, fmt};
+
+#[cfg(feature = "serde")]
+use std::io;
+
+/// Errors that can occur while using the [`TaskManager`](crate::TaskManager).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskManagerError {
+    /// The collection has not been registered.
+    ///
+    /// Holds the type name of the collection.
+    CollectionNotFound(&'static str),
+
+    /// The dynamic collection has not been added.
+    ///
+    /// Holds the key of the collection.
+    DynamicCollectionNotFound(String),
+
+    /// There is no collection with the key.
+    ///
+    /// Holds the key. See [`CollectionData::key`](crate::CollectionData::key).
+    KeyNotFound(String),
+
+    /// The task's return type doesn't match the collection's one.
+    TargetMismatch {
+        /// Collection's name.
+        collection: String,
+        /// The type that collection's tasks must return.
+        expected: &'static str,
+        /// The type that the task returns.
+        found: &'static str,
+    },
+
+    /// The [`TaskManager`](crate::TaskManager) that created the
+    /// [`TaskSpawner`](crate::TaskSpawner) has been dropped.
+    ManagerDropped,
+}
+
+impl fmt::Display for TaskManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskManagerError::CollectionNotFound(name) => write!(
+                f,
+                "You must add `{name}` collection to the `TaskManager` by calling `add_collection` or `register_collection`"
+            ),
+            TaskManagerError::DynamicCollectionNotFound(key) => write!(
+                f,
+                "Dynamic collection `{key}` has not been added to the `TaskManager`"
+            ),
+            TaskManagerError::KeyNotFound(key) => write!(
+                f,
+                "No collection with the key `{key}` has been added to the `TaskManager`"
+            ),
+            TaskManagerError::TargetMismatch {
+                collection,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Collection `{collection}` expects tasks that return `{expected}` but the task returns `{found}`"
+            ),
+            TaskManagerError::ManagerDropped => write!(f, "The `TaskManager` has been dropped"),
+        }
+    }
+}
+
+impl Error for TaskManagerError {}
+
+/// Errors that can occur while exporting the [`TaskHistory`](crate::TaskHistory).
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum ExportError {
+    /// Failed to write the file.
+    Io(io::Error),
+    /// Failed to serialize the history to JSON.
+    Json(serde_json::Error),
+    /// Failed to serialize the history to CSV.
+    Csv(csv::Error),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "Failed to write the history: {err}"),
+            ExportError::Json(err) => write!(f, "Failed to serialize the history to JSON: {err}"),
+            ExportError::Csv(err) => write!(f, "Failed to serialize the history to CSV: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::Io(err) => Some(err),
+            ExportError::Json(err) => Some(err),
+            ExportError::Csv(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<io::Error> for ExportError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ExportError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<csv::Error> for ExportError {
+    fn from(value: csv::Error) -> Self {
+        Self::Csv(value)
+    }
+}
+
+/// Errors that can occur while saving and restoring the queue of [`TaskSpec`](crate::TaskSpec)s.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum QueueError {
+    /// Failed to read or write the file.
+    Io(io::Error),
+    /// Failed to serialize or deserialize the specs or the task's parameters.
+    Json(serde_json::Error),
+    /// No builder has been registered for the task kind.
+    ///
+    /// Holds the kind.
+    UnknownKind(String),
+    /// Failed to push the task to its collection.
+    Manager(TaskManagerError),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueueError::Io(err) => write!(f, "Failed to access the queue file: {err}"),
+            QueueError::Json(err) => write!(f, "Failed to (de)serialize the queue: {err}"),
+            QueueError::UnknownKind(kind) => write!(
+                f,
+                "Task kind `{kind}` has not been registered with `register_task_kind`"
+            ),
+            QueueError::Manager(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for QueueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QueueError::Io(err) => Some(err),
+            QueueError::Json(err) => Some(err),
+            QueueError::UnknownKind(_) => None,
+            QueueError::Manager(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<io::Error> for QueueError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for QueueError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TaskManagerError> for QueueError {
+    fn from(value: TaskManagerError) -> Self {
+        Self::Manager(value)
+    }
+}
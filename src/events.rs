@@ -0,0 +1,104 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{execution::ProgressState, task::TaskId};
+
+/// Something that happened to a task of a collection.
+///
+/// Listeners can be added with [`CollectionData::subscribe`](crate::CollectionData::subscribe)
+/// and [`TaskManager::subscribe`](crate::TaskManager::subscribe).
+#[derive(Debug, Clone, Copy)]
+pub struct TaskEvent<'e> {
+    pub(crate) collection: &'e str,
+    pub(crate) id: TaskId,
+    pub(crate) name: &'e str,
+    pub(crate) kind: TaskEventKind<'e>,
+}
+
+impl<'e> TaskEvent<'e> {
+    /// Name of the collection the task belongs to.
+    pub fn collection(&self) -> &'e str {
+        self.collection
+    }
+
+    /// Task's id.
+    pub fn task_id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Task's name.
+    pub fn task_name(&self) -> &'e str {
+        self.name
+    }
+
+    /// What happened to the task.
+    pub fn kind(&self) -> TaskEventKind<'e> {
+        self.kind
+    }
+}
+
+/// Kind of a [`TaskEvent`].
+#[derive(Debug, Clone, Copy)]
+pub enum TaskEventKind<'e> {
+    /// The task was pushed to the collection's executor.
+    TaskQueued,
+    /// The task started its execution.
+    TaskStarted,
+    /// The task's progress was updated.
+    Progress(&'e ProgressState),
+    /// The task finished its execution.
+    TaskCompleted {
+        /// Time it took to execute the task.
+        duration: Duration,
+    },
+    /// The task panicked.
+    TaskFailed,
+    /// The task was cancelled while it was running or waiting in the queue.
+    TaskCancelled,
+}
+
+type Listener = Box<dyn FnMut(&TaskEvent<'_>)>;
+
+/// Listeners of a collection along with the ones shared by all collections of the manager.
+#[derive(Default)]
+pub(crate) struct Subscribers {
+    own: Vec<Listener>,
+    shared: SharedSubscribers,
+}
+
+impl Subscribers {
+    pub(crate) fn push(&mut self, listener: impl FnMut(&TaskEvent<'_>) + 'static) {
+        self.own.push(Box::new(listener));
+    }
+
+    pub(crate) fn set_shared(&mut self, shared: SharedSubscribers) {
+        self.shared = shared;
+    }
+
+    /// Checks if there's at least one listener.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.own.is_empty() && self.shared.0.borrow().is_empty()
+    }
+
+    pub(crate) fn emit(&mut self, event: TaskEvent<'_>) {
+        for listener in &mut self.own {
+            (listener)(&event)
+        }
+        self.shared.emit(event);
+    }
+}
+
+/// Listeners added to the [`TaskManager`](crate::TaskManager).
+#[derive(Default, Clone)]
+pub(crate) struct SharedSubscribers(Rc<RefCell<Vec<Listener>>>);
+
+impl SharedSubscribers {
+    pub(crate) fn push(&self, listener: impl FnMut(&TaskEvent<'_>) + 'static) {
+        self.0.borrow_mut().push(Box::new(listener));
+    }
+
+    fn emit(&self, event: TaskEvent<'_>) {
+        for listener in self.0.borrow_mut().iter_mut() {
+            (listener)(&event)
+        }
+    }
+}
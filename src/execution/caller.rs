@@ -1,6 +1,4 @@
-use std::{any::Any, future::Future};
-
-use crate::any::{HigherKinded, IntoAny};
+use std::future::Future;
 
 use super::{progress::TaskProgressShared, PinnedFuture};
 
@@ -14,7 +12,7 @@ pub enum Caller<T> {
     Standard(PinnedFuture<T>),
 
     /// Progressing caller. Has progress. Holds a closure that returns a future.
-    Progressing(Box<dyn FnOnce(TaskProgressShared) -> PinnedFuture<T>>),
+    Progressing(Box<dyn FnOnce(TaskProgressShared) -> PinnedFuture<T> + Send>),
 }
 
 impl<T> Caller<T> {
@@ -29,34 +27,22 @@ impl<T> Caller<T> {
     /// Create a [`Standard`](Self::Progressing) caller from a closure that returns a future.
     pub fn progressing<F, Fut>(fun: F) -> Self
     where
-        F: FnOnce(TaskProgressShared) -> Fut + 'static,
+        F: FnOnce(TaskProgressShared) -> Fut + Send + 'static,
         Fut: Future<Output = T> + Send + 'static,
     {
         Self::Progressing(Box::new(|progress| Box::pin((fun)(progress))))
     }
-}
-
-impl<T> HigherKinded for Caller<T> {
-    type T<A> = Caller<A>;
-}
 
-impl<U> IntoAny for Caller<U>
-where
-    U: Send + 'static,
-{
-    fn into_any(self) -> Self::T<Box<dyn Any + Send>> {
+    /// Replaces the caller's future with the one returned by `map`.
+    pub(crate) fn map_future<U, F, Fut>(self, map: F) -> Caller<U>
+    where
+        T: 'static,
+        F: FnOnce(PinnedFuture<T>) -> Fut + Send + 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
         match self {
-            Caller::Standard(fut) => {
-                Caller::standard(async { Box::new(fut.await) as Box<dyn Any + Send> })
-            }
-            Caller::Progressing(fun) => {
-                let fun = Box::new(|progress| {
-                    let fut = (fun)(progress);
-                    Box::pin(async move { Box::new(fut.await) as Box<dyn Any + Send> })
-                });
-
-                Caller::progressing(fun)
-            }
+            Caller::Standard(fut) => Caller::standard(map(fut)),
+            Caller::Progressing(fun) => Caller::progressing(move |progress| map((fun)(progress))),
         }
     }
 }
@@ -33,6 +33,10 @@ impl TaskExecutor for Linear {
     fn iter_tasks(&self) -> Box<dyn Iterator<Item = &AnyTask> + '_> {
         Box::new(self.inner.iter())
     }
+
+    fn clear(&mut self) {
+        self.inner.clear()
+    }
 }
 
 /// Provides parallel tasks execution.
@@ -58,4 +62,8 @@ impl TaskExecutor for Parallel {
     fn iter_tasks(&self) -> Box<dyn Iterator<Item = &AnyTask> + '_> {
         Box::new(self.inner.iter())
     }
+
+    fn clear(&mut self) {
+        self.inner.clear()
+    }
 }
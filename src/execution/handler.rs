@@ -1,39 +1,93 @@
-use std::any::Any;
-
-use crate::any::{HigherKinded, IntoAny};
-
-/// Handler that has `Box<dyn Any + Send>` as a type parameter.
-pub type AnyHandler<'h> = Handler<'h, Box<dyn Any + Send>>;
+use crate::TaskOutcome;
 
 /// Handler that is used to handle task's result.
-pub struct Handler<'h, T>(Box<dyn FnMut(T) + 'h>);
+pub struct Handler<'h, T>(Box<dyn FnMut(T, TaskOutcome) + 'h>);
 
 impl<'h, T: 'static> Handler<'h, T> {
     /// Creates a new handle.
-    pub fn new(handler: impl FnMut(T) + 'h) -> Self {
+    pub fn new(mut handler: impl FnMut(T) + 'h) -> Self {
+        Self(Box::new(move |value, _| (handler)(value)))
+    }
+
+    /// Creates a new handle that also receives the information about the task
+    /// that produced the value.
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use egui_task_manager::*;
+    /// struct Thumbnails;
+    ///
+    /// impl<'c> TasksCollection<'c> for Thumbnails {
+    ///     type Context = &'c mut HashMap<TaskId, String>;
+    ///     type Target = String;
+    ///     type Executor = executors::Parallel;
+    ///
+    ///     fn name() -> &'static str {
+    ///         "Thumbnails"
+    ///     }
+    ///
+    ///     fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+    ///         Handler::with_outcome(|thumbnail, outcome| {
+    ///             context.insert(outcome.id(), thumbnail);
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    pub fn with_outcome(handler: impl FnMut(T, TaskOutcome) + 'h) -> Self {
         Self(Box::new(handler))
     }
 
     /// Applies handle on some value
-    pub fn apply(&mut self, value: T) {
-        (self.0)(value)
+    pub fn apply(&mut self, value: T, outcome: TaskOutcome) {
+        (self.0)(value, outcome)
     }
 }
 
-impl<'h, T> HigherKinded for Handler<'h, T> {
-    type T<A> = Handler<'h, A>;
-}
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{testing::poll_until, *};
+
+    struct Thumbnails;
+
+    impl<'c> TasksCollection<'c> for Thumbnails {
+        type Context = &'c mut HashMap<TaskId, String>;
+        type Target = String;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Thumbnails"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::with_outcome(|thumbnail, outcome| {
+                context.insert(outcome.id(), thumbnail);
+            })
+        }
+    }
+
+    #[test]
+    fn outcome_identifies_the_task() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Thumbnails>();
+
+        let task = Task::new("a.png", Caller::standard(async { "a".to_owned() }));
+        let a = task.id();
+        manager.push_task::<Thumbnails>(task);
+        let task = Task::new("b.png", Caller::standard(async { "b".to_owned() }));
+        let b = task.id();
+        manager.push_task::<Thumbnails>(task);
 
-impl<'h, T> IntoAny for Handler<'h, T>
-where
-    T: 'static,
-{
-    fn into_any(mut self) -> Self::T<Box<dyn Any + Send>> {
-        let handler = Box::new(move |boxed_any: Box<dyn Any + Send>| {
-            let reference = boxed_any.downcast::<T>().unwrap();
-            (self.0)(*reference)
+        let mut thumbnails = HashMap::new();
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Thumbnails>(&mut thumbnails));
+            thumbnails.len() == 2
         });
 
-        Handler(handler)
+        assert_eq!(thumbnails[&a], "a");
+        assert_eq!(thumbnails[&b], "b");
     }
 }
@@ -0,0 +1,182 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::mpsc::{Receiver, Sender},
+    time::Instant,
+};
+
+use crate::channel::Channel;
+
+/// Level of a [`LogLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Very detailed information.
+    Trace,
+    /// Information useful for debugging.
+    Debug,
+    /// General information.
+    Info,
+    /// Something unexpected happened but the task can continue.
+    Warn,
+    /// Something went wrong.
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+        f.write_str(level)
+    }
+}
+
+/// A message emitted by a running task.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    level: LogLevel,
+    message: String,
+    time: Instant,
+}
+
+impl LogLine {
+    /// Creates a new line. The time is set to now.
+    pub fn new(level: LogLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            time: Instant::now(),
+        }
+    }
+
+    /// Line's level.
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    /// Line's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The moment when the line was emitted.
+    pub fn time(&self) -> Instant {
+        self.time
+    }
+}
+
+/// Bounded log of a task.
+///
+/// It keeps only the last [`capacity`](Self::capacity) lines, older ones are dropped.
+/// Lines can be sent using [`TaskProgressShared::log`](crate::TaskProgressShared::log).
+///
+/// ```rust
+/// # use egui_task_manager::{LogLevel, LogLine, TaskLog};
+/// let mut log = TaskLog::new(2);
+///
+/// let sender = log.sender();
+/// for message in ["Scanning", "Copying", "Done"] {
+///     let _ = sender.send(LogLine::new(LogLevel::Info, message));
+/// }
+///
+/// log.receive();
+/// let messages = log.iter().map(|line| line.message()).collect::<Vec<_>>();
+/// assert_eq!(messages, ["Copying", "Done"]);
+/// ```
+pub struct TaskLog {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    channel: Channel<LogLine>,
+}
+
+impl Default for TaskLog {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl TaskLog {
+    /// Default number of lines that are kept.
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    /// Creates an empty log that keeps at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity,
+            channel: Channel::new(),
+        }
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws a simple ui.
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .max_height(150.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for line in &self.lines {
+                    let color = match line.level {
+                        LogLevel::Trace | LogLevel::Debug => ui.visuals().weak_text_color(),
+                        LogLevel::Info => ui.visuals().text_color(),
+                        LogLevel::Warn => ui.visuals().warn_fg_color,
+                        LogLevel::Error => ui.visuals().error_fg_color,
+                    };
+                    ui.colored_label(color, format!("[{}] {}", line.level, line.message));
+                }
+            });
+    }
+
+    /// Maximum number of lines.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of stored lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Checks if there's no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// An iterator visiting stored lines from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &LogLine> {
+        self.lines.iter()
+    }
+
+    /// Log channel's sender.
+    pub fn sender(&self) -> Sender<LogLine> {
+        self.channel.sender()
+    }
+
+    /// Log channel's receiver.
+    pub fn receiver(&self) -> &Receiver<LogLine> {
+        self.channel.receiver()
+    }
+
+    /// Pushes a new line dropping the oldest one if the log is full.
+    pub fn push(&mut self, line: LogLine) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Receives all sent lines.
+    pub fn receive(&mut self) {
+        while let Ok(line) = self.channel.receiver().try_recv() {
+            self.push(line)
+        }
+    }
+}
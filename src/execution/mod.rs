@@ -5,16 +5,25 @@ use crate::task::{AnyTask, TaskData};
 mod caller;
 pub mod executors;
 mod handler;
+mod log;
 mod progress;
+mod prompt;
 
 pub use caller::Caller;
 pub use handler::*;
+pub use log::*;
 pub use progress::*;
+pub use prompt::*;
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
 
-/// Marker to determine if the task is finished.
-pub(super) struct Finished;
+/// Marker to determine if the task is finished and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Finished {
+    Completed,
+    Cancelled,
+    Failed,
+}
 
 const _: Option<Box<dyn TaskExecutor>> = None;
 
@@ -35,6 +44,13 @@ pub trait TaskExecutor {
 
     /// Tasks that are currently waiting to be executed.
     fn iter_tasks(&self) -> Box<dyn Iterator<Item = &AnyTask> + '_>;
+
+    /// Removes all tasks that are waiting to be executed.
+    ///
+    /// The default implementation polls the executor until it returns [`ExecutionPoll::Pending`].
+    fn clear(&mut self) {
+        while let ExecutionPoll::Ready(_) = self.poll(&[]) {}
+    }
 }
 
 /// Indicates whether a task available to be executed or not.
@@ -1,34 +1,394 @@
-use std::sync::{
-    mpsc::{Receiver, SendError, Sender},
-    Arc, OnceLock,
+use std::{
+    borrow::Cow,
+    sync::mpsc::{Receiver, SendError, Sender},
+    time::{Duration, Instant},
 };
 
 use crate::channel::Channel;
 
+use super::{
+    log::{LogLevel, LogLine},
+    prompt::{Answer, Prompt, Question},
+};
+
 /// It is used to handle to execution progress.
 ///
 /// # Usage
 ///
 /// ```rust
-/// # use egui_task_manager::Progress;
+/// # use egui_task_manager::{Progress, ProgressState};
 /// struct UnitProgress;
 ///
 /// impl Progress for UnitProgress {
-///     fn apply(&self, current: &mut u32) {
-///         *current += 1;
+///     fn apply(&self, state: &mut ProgressState) {
+///         state.advance(1);
 ///     }
 /// }
 /// ```
+///
+/// It is also implemented for closures so you don't have to define a type for every update.
+/// ```rust
+/// # use egui_task_manager::{Progress, ProgressState};
+/// fn assert_progress(_: impl Progress) {}
+///
+/// assert_progress(|state: &mut ProgressState| state.set_stage("Copying"));
+/// ```
 pub trait Progress: Send {
     /// Apply the progress.
-    fn apply(&self, current: &mut u32);
+    fn apply(&self, state: &mut ProgressState);
+}
+
+impl<F> Progress for F
+where
+    F: Fn(&mut ProgressState) + Send,
+{
+    fn apply(&self, state: &mut ProgressState) {
+        (self)(state)
+    }
+}
+
+/// Determines how the values of the [`ProgressState`] are interpreted and displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressUnit {
+    /// Abstract items or steps. Displayed as `current/total`.
+    #[default]
+    Items,
+    /// Bytes. Displayed as `1.2 MiB/4.0 GiB`.
+    Bytes,
+    /// A fraction of the work. Displayed as a percentage.
+    ///
+    /// See [`ProgressState::set_fraction`].
+    Fraction,
+}
+
+/// The state of the task's progress.
+///
+/// The total might be unknown (indeterminate progress), set later and changed
+/// at any moment. It also holds an optional stage that describes what the task
+/// is currently doing, e.g. "Scanning" and then "Copying".
+///
+/// ```rust
+/// # use egui_task_manager::{ProgressState, ProgressUnit};
+/// let mut state = ProgressState::default();
+/// assert_eq!(state.fraction(), None);
+///
+/// state.set_unit(ProgressUnit::Bytes);
+/// state.set_total(Some(8 * 1024 * 1024 * 1024));
+/// state.advance(2 * 1024 * 1024 * 1024);
+/// assert_eq!(state.fraction(), Some(0.25));
+///
+/// state.set_stage("Extracting");
+/// state.set_indeterminate();
+/// assert_eq!(state.fraction(), None);
+/// assert_eq!(state.stage(), Some("Extracting"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProgressState {
+    current: u64,
+    total: Option<u64>,
+    unit: ProgressUnit,
+    stage: Option<String>,
+}
+
+impl ProgressState {
+    /// The resolution used by the [`Fraction`](ProgressUnit::Fraction) unit.
+    pub const FRACTION_SCALE: u64 = 10_000;
+
+    /// Current progress.
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Total value if it's known.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Progress unit.
+    pub fn unit(&self) -> ProgressUnit {
+        self.unit
+    }
+
+    /// Current stage of the task if it was set.
+    pub fn stage(&self) -> Option<&str> {
+        self.stage.as_deref()
+    }
+
+    /// Checks if the total is unknown.
+    pub fn is_indeterminate(&self) -> bool {
+        self.total.is_none()
+    }
+
+    /// Returns the completed fraction in range `0.0..=1.0` or `None` if the
+    /// progress is indeterminate.
+    pub fn fraction(&self) -> Option<f32> {
+        self.total.map(|total| match total {
+            0 => 1.0,
+            total => (self.current.min(total) as f64 / total as f64) as f32,
+        })
+    }
+
+    /// Sets the current progress.
+    pub fn set_current(&mut self, current: u64) {
+        self.current = current;
+    }
+
+    /// Increases the current progress by `amount`.
+    pub fn advance(&mut self, amount: u64) {
+        self.current = self.current.saturating_add(amount);
+    }
+
+    /// Sets the total value. `None` makes the progress indeterminate.
+    pub fn set_total(&mut self, total: Option<u64>) {
+        self.total = total;
+    }
+
+    /// Makes the progress indeterminate again. The current value is kept.
+    pub fn set_indeterminate(&mut self) {
+        self.total = None;
+    }
+
+    /// Sets the progress unit.
+    pub fn set_unit(&mut self, unit: ProgressUnit) {
+        self.unit = unit;
+    }
+
+    /// Switches to the [`Fraction`](ProgressUnit::Fraction) unit and sets the
+    /// completed fraction. The value is clamped to `0.0..=1.0`.
+    pub fn set_fraction(&mut self, fraction: f32) {
+        self.unit = ProgressUnit::Fraction;
+        self.total = Some(Self::FRACTION_SCALE);
+        self.current =
+            (fraction.clamp(0.0, 1.0) as f64 * Self::FRACTION_SCALE as f64).round() as u64;
+    }
+
+    /// Sets the current stage.
+    pub fn set_stage(&mut self, stage: impl Into<String>) {
+        self.stage = Some(stage.into());
+    }
+
+    /// Removes the current stage.
+    pub fn clear_stage(&mut self) {
+        self.stage = None;
+    }
+
+    /// Formats the progress according to its [`ProgressUnit`].
+    ///
+    /// ```rust
+    /// # use egui_task_manager::{ProgressState, ProgressUnit};
+    /// let mut state = ProgressState::default();
+    /// state.set_total(Some(10));
+    /// state.advance(3);
+    /// assert_eq!(state.text(), "3/10");
+    ///
+    /// state.set_unit(ProgressUnit::Bytes);
+    /// state.set_current(1536);
+    /// state.set_total(None);
+    /// assert_eq!(state.text(), "1.5 KiB");
+    ///
+    /// state.set_fraction(0.425);
+    /// assert_eq!(state.text(), "42%");
+    /// ```
+    pub fn text(&self) -> String {
+        match (self.unit, self.total) {
+            (ProgressUnit::Fraction, _) => {
+                format!("{}%", (self.fraction().unwrap_or(0.0) * 100.0) as u32)
+            }
+            (ProgressUnit::Items, Some(total)) => format!("{}/{}", self.current, total),
+            (ProgressUnit::Items, None) => self.current.to_string(),
+            (ProgressUnit::Bytes, Some(total)) => {
+                format!("{}/{}", format_bytes(self.current), format_bytes(total))
+            }
+            (ProgressUnit::Bytes, None) => format_bytes(self.current),
+        }
+    }
+}
+
+/// Formats the number of bytes using binary prefixes.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Formats the duration in a short human readable form, e.g. `1h 3m`, `2m 5s` or `40s`.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s}s"),
+        (h, m, _) => format!("{h}h {m}m"),
+    }
+}
+
+/// Smoothed rate of the progress computed from the timestamps of the updates.
+///
+/// It uses an exponential moving average so a single slow or fast update
+/// doesn't make the estimate jump. The rate goes down while no progress is made
+/// for longer than the interval between the last two updates.
+///
+/// ```rust
+/// # use std::time::{Duration, Instant};
+/// # use egui_task_manager::{ProgressState, Throughput};
+/// let start = Instant::now();
+/// let mut throughput = Throughput::new(start);
+///
+/// let mut state = ProgressState::default();
+/// state.set_total(Some(100));
+/// state.set_current(10);
+/// throughput.record(start + Duration::from_secs(1), state.current());
+///
+/// assert_eq!(throughput.rate(), Some(10.0));
+/// assert_eq!(throughput.eta(&state), Some(Duration::from_secs(9)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Throughput {
+    last: (Instant, u64),
+    interval: Duration,
+    rate: Option<f64>,
+}
+
+impl Throughput {
+    /// How fast older samples lose their weight.
+    const TIME_CONSTANT: f64 = 3.0;
+
+    /// Creates a new throughput starting at `start` with zero progress.
+    pub fn new(start: Instant) -> Self {
+        Self {
+            last: (start, 0),
+            interval: Duration::ZERO,
+            rate: None,
+        }
+    }
+
+    /// Records the progress value at the moment `now`.
+    ///
+    /// If the value decreases (e.g. a new stage has started) the estimation starts over.
+    pub fn record(&mut self, now: Instant, current: u64) {
+        let (last_time, last_current) = self.last;
+
+        if current < last_current {
+            *self = Self::new(now);
+            self.last.1 = current;
+            return;
+        }
+
+        let interval = now.saturating_duration_since(last_time);
+        let elapsed = interval.as_secs_f64();
+        if elapsed <= 0.0 || current == last_current {
+            return;
+        }
+
+        let instant_rate = (current - last_current) as f64 / elapsed;
+        self.rate = Some(match self.rate {
+            Some(rate) => {
+                let alpha = 1.0 - (-elapsed / Self::TIME_CONSTANT).exp();
+                alpha * instant_rate + (1.0 - alpha) * rate
+            }
+            None => instant_rate,
+        });
+        self.last = (now, current);
+        self.interval = interval;
+    }
+
+    /// Smoothed number of units per second if at least one update was recorded.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate_at(Instant::now())
+    }
+
+    /// Smoothed number of units per second at the moment `now`.
+    pub fn rate_at(&self, now: Instant) -> Option<f64> {
+        let idle = now
+            .saturating_duration_since(self.last.0)
+            .saturating_sub(self.interval);
+        let decay = (-idle.as_secs_f64() / Self::TIME_CONSTANT).exp();
+        self.rate.map(|rate| rate * decay)
+    }
+
+    /// Estimated time remaining for the provided state.
+    ///
+    /// Returns `None` if the total or the rate is unknown, or the estimate is too large.
+    pub fn eta(&self, state: &ProgressState) -> Option<Duration> {
+        self.eta_at(Instant::now(), state)
+    }
+
+    /// Estimated time remaining for the provided state at the moment `now`.
+    pub fn eta_at(&self, now: Instant, state: &ProgressState) -> Option<Duration> {
+        let rate = self.rate_at(now).filter(|rate| *rate > 0.0)?;
+        let remaining = state.total()?.saturating_sub(state.current());
+        Duration::try_from_secs_f64(remaining as f64 / rate).ok()
+    }
+
+    /// Formats the rate and the estimated time according to the state's [`ProgressUnit`],
+    /// e.g. `12.3 MiB/s, ~40s left`.
+    pub fn text(&self, state: &ProgressState) -> Option<String> {
+        let now = Instant::now();
+        let rate = self.rate_at(now).map(|rate| match state.unit() {
+            ProgressUnit::Items => format!("{rate:.1}/s"),
+            ProgressUnit::Bytes => format!("{}/s", format_bytes(rate as u64)),
+            ProgressUnit::Fraction => format!(
+                "{:.1}%/s",
+                rate * 100.0 / ProgressState::FRACTION_SCALE as f64
+            ),
+        });
+        let eta = self
+            .eta_at(now, state)
+            .map(|eta| format!("~{} left", format_duration(eta)));
+
+        match (rate, eta) {
+            (Some(rate), Some(eta)) => Some(format!("{rate}, {eta}")),
+            (rate, eta) => rate.or(eta),
+        }
+    }
+}
+
+/// A child progress created by [`TaskProgressShared::child`].
+///
+/// It takes `weight` share of its parent.
+pub struct SubProgress {
+    name: String,
+    weight: f32,
+    progress: TaskProgress,
+}
+
+impl SubProgress {
+    /// Child's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Child's share of the parent.
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Child's progress.
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
 }
 
 /// Execution progress of a task.
+///
+/// It might have children created by [`TaskProgressShared::child`]. In that case
+/// the progress is aggregated from them, see [`combined_state`](Self::combined_state).
 pub struct TaskProgress {
-    current: u32,
-    total: Arc<OnceLock<u32>>,
+    state: ProgressState,
+    throughput: Throughput,
     channel: Channel<Box<dyn Progress>>,
+    children: Vec<SubProgress>,
+    children_channel: Channel<SubProgress>,
 }
 
 impl Default for TaskProgress {
@@ -41,37 +401,129 @@ impl TaskProgress {
     /// Returns an empty task progress.
     pub fn new() -> Self {
         Self {
-            current: 0,
-            total: Arc::new(OnceLock::new()),
+            state: ProgressState::default(),
+            throughput: Throughput::new(Instant::now()),
             channel: Channel::new(),
+            children: Vec::new(),
+            children_channel: Channel::new(),
         }
     }
 
     #[cfg(feature = "egui")]
     /// Draws a simple ui.
     ///
-    /// # Panics
-    ///
-    /// It will panic in the debug builds if `self.total` is uninitialized.
+    /// Shows the stage if it's set, a progress bar if the total is known
+    /// and a spinner otherwise. The rate and the estimated time are displayed
+    /// as soon as they are known. Children can be expanded below the bar.
     pub fn ui(&self, ui: &mut egui::Ui) {
-        if let Some(total) = self.total.get().copied() {
-            ui.add(
-                egui::ProgressBar::new(self.current as f32 / total as f32)
-                    .text(format!("{}/{}", self.current, total)),
-            );
-        } else {
-            ui.spinner();
+        let state = self.combined_state();
+
+        if let Some(stage) = state.stage() {
+            ui.label(stage);
+        }
+
+        let text = match self.throughput.text(&state) {
+            Some(throughput) => format!("{} ({throughput})", state.text()),
+            None => state.text(),
+        };
+
+        match state.fraction() {
+            Some(fraction) => {
+                ui.add(egui::ProgressBar::new(fraction).text(text));
+            }
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    if state.current() > 0 {
+                        ui.label(text);
+                    }
+                });
+            }
+        }
+
+        if !self.children.is_empty() {
+            egui::CollapsingHeader::new("Details")
+                .id_source(ui.id().with("progress_children"))
+                .show(ui, |ui| {
+                    for child in &self.children {
+                        ui.label(child.name());
+                        child.progress().ui(ui);
+                    }
+                });
+        }
+    }
+
+    /// Current state of the progress.
+    pub fn state(&self) -> &ProgressState {
+        &self.state
+    }
+
+    /// Mutable reference to the current state of the progress.
+    pub fn state_mut(&mut self) -> &mut ProgressState {
+        &mut self.state
+    }
+
+    /// Children of the progress.
+    pub fn children(&self) -> &[SubProgress] {
+        &self.children
+    }
+
+    /// The state that takes children into account.
+    ///
+    /// If there's no children it's the same as [`state`](Self::state). Otherwise
+    /// it has the [`Fraction`](ProgressUnit::Fraction) unit and each child
+    /// contributes according to its weight. If the weights sum up to less than `1.0`
+    /// the rest is taken by the parent's own state.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::TaskProgress;
+    /// let mut progress = TaskProgress::new();
+    ///
+    /// let shared = progress.share();
+    /// let download = shared.child("Download", 0.3);
+    /// let extract = shared.child("Extract", 0.7);
+    ///
+    /// let _ = download.set_fraction(1.0);
+    /// let _ = extract.set_total(10);
+    /// let _ = extract.advance(5);
+    ///
+    /// progress.apply_updates();
+    /// assert_eq!(progress.children().len(), 2);
+    /// assert_eq!(progress.combined_state().fraction(), Some(0.65));
+    /// ```
+    pub fn combined_state(&self) -> Cow<'_, ProgressState> {
+        if self.children.is_empty() {
+            return Cow::Borrowed(&self.state);
         }
+
+        let weights = self.children.iter().map(|child| child.weight).sum::<f32>();
+        let own_share = (1.0 - weights).max(0.0);
+
+        let completed = self
+            .children
+            .iter()
+            .map(|child| child.weight * child.progress.combined_state().fraction().unwrap_or(0.0))
+            .sum::<f32>()
+            + own_share * self.state.fraction().unwrap_or(0.0);
+
+        let mut state = self.state.clone();
+        state.set_fraction(completed / (weights + own_share));
+        Cow::Owned(state)
+    }
+
+    /// Throughput computed from the progress updates.
+    pub fn throughput(&self) -> &Throughput {
+        &self.throughput
     }
 
-    /// Mutable reference to the current progress.
-    pub fn current_mut(&mut self) -> &mut u32 {
-        &mut self.current
+    /// Smoothed number of units per second.
+    pub fn rate(&self) -> Option<f64> {
+        self.throughput.rate()
     }
 
-    /// Sets the total value.
-    pub fn set_total(&self, total: u32) -> Result<(), u32> {
-        self.total.set(total)
+    /// Estimated time remaining.
+    pub fn eta(&self) -> Option<Duration> {
+        self.throughput.eta(&self.combined_state())
     }
 
     /// Progress channel's sender.
@@ -84,11 +536,47 @@ impl TaskProgress {
         self.channel.receiver()
     }
 
+    /// Applies all received updates to the state and its children
+    /// and records the throughput.
+    ///
+    /// Returns `true` if any updates were received.
+    pub fn apply_updates(&mut self) -> bool {
+        let now = Instant::now();
+        let mut updated = false;
+
+        for update in self.channel.receiver().try_iter() {
+            update.apply(&mut self.state);
+            updated = true;
+        }
+
+        for child in self.children_channel.receiver().try_iter() {
+            // The values are measured differently after the first child appears.
+            if self.children.is_empty() {
+                self.throughput = Throughput::new(now);
+            }
+            self.children.push(child);
+            updated = true;
+        }
+
+        for child in &mut self.children {
+            updated |= child.progress.apply_updates();
+        }
+
+        if updated {
+            let current = self.combined_state().current();
+            self.throughput.record(now, current);
+        }
+
+        updated
+    }
+
     /// Gives a shared access to the [`TaskProgress`].
     pub fn share(&self) -> TaskProgressShared {
         TaskProgressShared {
-            total: self.total.clone(),
             sender: self.sender(),
+            children: self.children_channel.sender(),
+            log: None,
+            questions: None,
         }
     }
 }
@@ -104,28 +592,158 @@ impl TaskProgress {
 /// struct UnitProgress;
 ///
 /// impl Progress for UnitProgress {
-///     fn apply(&self, current: &mut u32) {
-///         *current += 1;
+///     fn apply(&self, state: &mut ProgressState) {
+///         state.advance(1);
 ///     }
 /// }
 ///
 /// Caller::progressing(|progress| async move {
 ///     // Set the total number of items or steps that needs to be completed
 ///     // eg. number of items in the downloading.
-///     progress.set_total(5);
+///     let _ = progress.set_total(5);
 ///     // Now we can use our type.
 ///     let _ = progress.update(UnitProgress);
+///
+///     // Or use one of the built-in updates.
+///     let _ = progress.set_stage("Copying");
+///     let _ = progress.advance(1);
 /// });
 /// ```
+///
+/// Composite tasks can split the progress into weighted children.
+/// See [`child`](Self::child).
 pub struct TaskProgressShared {
-    total: Arc<OnceLock<u32>>,
     sender: Sender<Box<dyn Progress>>,
+    children: Sender<SubProgress>,
+    log: Option<Sender<LogLine>>,
+    questions: Option<Sender<Question>>,
 }
 
 impl TaskProgressShared {
-    /// Sets the total value.
-    pub fn set_total(&self, total: u32) -> Result<(), u32> {
-        self.total.set(total)
+    /// Sets the total value. It can be called again if the total changes.
+    pub fn set_total(&self, total: u64) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(move |state: &mut ProgressState| state.set_total(Some(total)))
+    }
+
+    /// Makes the progress indeterminate again.
+    pub fn set_indeterminate(&self) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(|state: &mut ProgressState| state.set_indeterminate())
+    }
+
+    /// Sets the current progress.
+    pub fn set_current(&self, current: u64) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(move |state: &mut ProgressState| state.set_current(current))
+    }
+
+    /// Increases the current progress by `amount`.
+    pub fn advance(&self, amount: u64) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(move |state: &mut ProgressState| state.advance(amount))
+    }
+
+    /// Sets the progress unit.
+    pub fn set_unit(&self, unit: ProgressUnit) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(move |state: &mut ProgressState| state.set_unit(unit))
+    }
+
+    /// Sets the completed fraction. See [`ProgressState::set_fraction`].
+    pub fn set_fraction(&self, fraction: f32) -> Result<(), SendError<Box<dyn Progress>>> {
+        self.update(move |state: &mut ProgressState| state.set_fraction(fraction))
+    }
+
+    /// Sets the current stage.
+    pub fn set_stage(&self, stage: impl Into<String>) -> Result<(), SendError<Box<dyn Progress>>> {
+        let stage = stage.into();
+        self.update(move |state: &mut ProgressState| state.set_stage(stage.clone()))
+    }
+
+    /// Creates a child progress that takes `weight` share of this one,
+    /// e.g. `0.3` for downloading and `0.7` for extracting.
+    ///
+    /// The child reports on its own and the parent aggregates all of its children.
+    /// Children can have their own children.
+    pub fn child(&self, name: impl Into<String>, weight: f32) -> TaskProgressShared {
+        let progress = TaskProgress::new();
+        let shared = TaskProgressShared {
+            log: self.log.clone(),
+            questions: self.questions.clone(),
+            ..progress.share()
+        };
+
+        let _ = self.children.send(SubProgress {
+            name: name.into(),
+            weight: weight.max(0.0),
+            progress,
+        });
+
+        shared
+    }
+
+    /// Sends a line to the task's log which can be seen in the ui.
+    ///
+    /// Lines are silently dropped if the log is not available.
+    /// See [`TaskData::log`](crate::TaskData::log).
+    pub fn log(&self, level: LogLevel, message: impl Into<String>) {
+        if let Some(log) = &self.log {
+            let _ = log.send(LogLine::new(level, message));
+        }
+    }
+
+    /// Sends a line with the [`Debug`](LogLevel::Debug) level.
+    pub fn debug(&self, message: impl Into<String>) {
+        self.log(LogLevel::Debug, message)
+    }
+
+    /// Sends a line with the [`Info`](LogLevel::Info) level.
+    pub fn info(&self, message: impl Into<String>) {
+        self.log(LogLevel::Info, message)
+    }
+
+    /// Sends a line with the [`Warn`](LogLevel::Warn) level.
+    pub fn warn(&self, message: impl Into<String>) {
+        self.log(LogLevel::Warn, message)
+    }
+
+    /// Sends a line with the [`Error`](LogLevel::Error) level.
+    pub fn error(&self, message: impl Into<String>) {
+        self.log(LogLevel::Error, message)
+    }
+
+    /// Asks the user a question and waits for the answer.
+    ///
+    /// The question is displayed by [`TaskData::ui`](crate::TaskData::ui) or it can be
+    /// answered manually, see [`TaskData::questions`](crate::TaskData::questions).
+    ///
+    /// Returns `None` if the question was dismissed or it can't be asked.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// Caller::progressing(|progress| async move {
+    ///     let answer = progress.ask(Prompt::confirm("Overwrite file?")).await;
+    ///     answer == Some(Answer::Confirmed(true))
+    /// });
+    /// ```
+    pub async fn ask(&self, prompt: Prompt) -> Option<Answer> {
+        let questions = self.questions.as_ref()?;
+        let (question, answer) = Question::new(prompt);
+
+        questions.send(question).ok()?;
+        answer.await.ok()
+    }
+
+    /// Connects the handle to the task's questions.
+    pub(crate) fn with_questions(self, questions: Sender<Question>) -> Self {
+        Self {
+            questions: Some(questions),
+            ..self
+        }
+    }
+
+    /// Connects the handle to the task's log.
+    pub(crate) fn with_log(self, log: Sender<LogLine>) -> Self {
+        Self {
+            log: Some(log),
+            ..self
+        }
     }
 
     /// Progresses in the task.
@@ -136,11 +754,6 @@ impl TaskProgressShared {
         self.sender.send(Box::new(progress))
     }
 
-    /// Get the total value.
-    pub fn total(&self) -> Option<u32> {
-        self.total.get().copied()
-    }
-
     /// Clones the sender and returns it.
     ///
     /// It is recommended to use [`update`](Self::update).
@@ -148,3 +761,103 @@ impl TaskProgressShared {
         self.sender.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::poll_until, *};
+
+    struct Files;
+
+    impl<'c> TasksCollection<'c> for Files {
+        type Context = &'c mut Vec<Option<Answer>>;
+        type Target = Option<Answer>;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Files"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|answer| context.push(answer))
+        }
+    }
+
+    #[test]
+    fn ask_resolves_to_the_answer() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Files>();
+
+        let save = || {
+            Task::new(
+                "Save",
+                Caller::progressing(|progress| async move {
+                    progress.ask(Prompt::confirm("Overwrite file?")).await
+                }),
+            )
+        };
+        manager.push_task::<Files>(save());
+        manager.push_task::<Files>(save());
+
+        let mut answers = Vec::new();
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Files>(&mut answers));
+            let tasks = manager.get_collection::<Files>().tasks();
+            tasks.iter().all(|task| task.questions().len() == 1)
+        });
+
+        let tasks = manager.get_collection::<Files>().tasks();
+        tasks[0].questions()[0].answer(Answer::Confirmed(true));
+        tasks[1].questions()[0].dismiss();
+
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Files>(&mut answers));
+            answers.len() == 2
+        });
+        assert!(answers.contains(&Some(Answer::Confirmed(true))));
+        assert!(answers.contains(&None));
+    }
+
+    #[test]
+    fn eta_of_a_huge_total_is_unknown() {
+        let start = Instant::now();
+        let now = start + Duration::from_secs(2);
+        let mut throughput = Throughput::new(start);
+        throughput.record(now, 1);
+
+        let mut state = ProgressState::default();
+        state.set_total(Some(u64::MAX));
+        state.set_current(1);
+
+        assert_eq!(throughput.rate_at(now), Some(0.5));
+        assert_eq!(throughput.eta_at(now, &state), None);
+        assert!(throughput.text(&state).is_some());
+    }
+
+    #[test]
+    fn rate_goes_down_while_stalled() {
+        let start = Instant::now();
+        let mut throughput = Throughput::new(start);
+        for second in 1..=3 {
+            throughput.record(start + Duration::from_secs(second), second * 10);
+        }
+
+        let mut state = ProgressState::default();
+        state.set_total(Some(100));
+        state.set_current(30);
+
+        let updated = start + Duration::from_secs(3);
+        let rate = throughput.rate_at(updated).unwrap();
+        let eta = throughput.eta_at(updated, &state).unwrap();
+
+        // No progress is expected until the usual interval passes.
+        let next_update = updated + Duration::from_secs(1);
+        assert_eq!(throughput.rate_at(next_update), Some(rate));
+
+        let stalled = updated + Duration::from_secs(30);
+        assert!(throughput.rate_at(stalled).unwrap() < rate / 100.0);
+        assert!(throughput.eta_at(stalled, &state).unwrap() > eta * 100);
+    }
+}
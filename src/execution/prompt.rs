@@ -0,0 +1,214 @@
+use std::sync::{
+    mpsc::{Receiver, Sender},
+    Mutex,
+};
+
+use tokio::sync::oneshot;
+
+use crate::channel::Channel;
+
+/// A question that a running task can ask the user.
+///
+/// See [`TaskProgressShared::ask`](crate::TaskProgressShared::ask).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prompt {
+    /// A yes/no question. Answered with [`Answer::Confirmed`].
+    Confirm(String),
+    /// A question with several options. Answered with [`Answer::Chosen`].
+    Choose {
+        /// The question itself.
+        message: String,
+        /// Available options.
+        options: Vec<String>,
+    },
+    /// A question that requires a text input. Answered with [`Answer::Input`].
+    Input(String),
+}
+
+impl Prompt {
+    /// Creates a [`Confirm`](Self::Confirm) prompt.
+    pub fn confirm(message: impl Into<String>) -> Self {
+        Self::Confirm(message.into())
+    }
+
+    /// Creates a [`Choose`](Self::Choose) prompt.
+    pub fn choose<I, S>(message: impl Into<String>, options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Choose {
+            message: message.into(),
+            options: options.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates an [`Input`](Self::Input) prompt.
+    pub fn input(message: impl Into<String>) -> Self {
+        Self::Input(message.into())
+    }
+
+    /// The question's text.
+    pub fn message(&self) -> &str {
+        match self {
+            Prompt::Confirm(message) | Prompt::Input(message) => message,
+            Prompt::Choose { message, .. } => message,
+        }
+    }
+}
+
+/// User's answer to a [`Prompt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// Answer to the [`Prompt::Confirm`].
+    Confirmed(bool),
+    /// Index of the chosen option of the [`Prompt::Choose`].
+    Chosen(usize),
+    /// Answer to the [`Prompt::Input`].
+    Input(String),
+}
+
+/// A question asked by a task that is waiting for the answer.
+///
+/// Questions are displayed by [`TaskData::ui`](crate::TaskData::ui). If you draw your own ui
+/// use [`TaskData::questions`](crate::TaskData::questions) and answer them using [`Question::answer`].
+pub struct Question {
+    prompt: Prompt,
+    responder: Mutex<Option<oneshot::Sender<Answer>>>,
+}
+
+impl Question {
+    pub(crate) fn new(prompt: Prompt) -> (Self, oneshot::Receiver<Answer>) {
+        let (sender, receiver) = oneshot::channel();
+        let question = Self {
+            prompt,
+            responder: Mutex::new(Some(sender)),
+        };
+        (question, receiver)
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws a simple ui.
+    ///
+    /// The text of the [`Prompt::Input`] is kept by the `ui` under the question's message,
+    /// use [`Ui::push_id`](egui::Ui::push_id) to separate the questions of different tasks.
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        ui.label(self.prompt.message());
+
+        match &self.prompt {
+            Prompt::Confirm(_) => {
+                ui.horizontal(|ui| {
+                    if ui.button("Yes").clicked() {
+                        self.answer(Answer::Confirmed(true));
+                    }
+                    if ui.button("No").clicked() {
+                        self.answer(Answer::Confirmed(false));
+                    }
+                });
+            }
+            Prompt::Choose { options, .. } => {
+                ui.horizontal_wrapped(|ui| {
+                    for (index, option) in options.iter().enumerate() {
+                        if ui.button(option).clicked() {
+                            self.answer(Answer::Chosen(index));
+                        }
+                    }
+                });
+            }
+            Prompt::Input(_) => {
+                let id = ui.id().with("question_input").with(self.prompt.message());
+                let mut input = ui.data_mut(|data| data.get_temp::<String>(id).unwrap_or_default());
+
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut input);
+                    if ui.button("Submit").clicked() {
+                        self.answer(Answer::Input(std::mem::take(&mut input)));
+                    }
+                });
+
+                ui.data_mut(|data| data.insert_temp(id, input));
+            }
+        }
+    }
+
+    /// The question's prompt.
+    pub fn prompt(&self) -> &Prompt {
+        &self.prompt
+    }
+
+    /// Sends the answer back to the task.
+    ///
+    /// Only the first answer is delivered, subsequent calls do nothing.
+    pub fn answer(&self, answer: Answer) {
+        if let Some(responder) = self.take_responder() {
+            let _ = responder.send(answer);
+        }
+    }
+
+    /// Dismisses the question without answering. The task will receive `None`.
+    pub fn dismiss(&self) {
+        self.take_responder();
+    }
+
+    /// Checks if the question was answered or dismissed, or the task
+    /// is no longer waiting for the answer.
+    pub fn is_closed(&self) -> bool {
+        self.responder
+            .lock()
+            .map(|responder| match responder.as_ref() {
+                Some(responder) => responder.is_closed(),
+                None => true,
+            })
+            .unwrap_or(true)
+    }
+
+    fn take_responder(&self) -> Option<oneshot::Sender<Answer>> {
+        self.responder
+            .lock()
+            .ok()
+            .and_then(|mut responder| responder.take())
+    }
+}
+
+/// Questions of a task that are waiting for the answer.
+pub struct TaskQuestions {
+    pending: Vec<Question>,
+    channel: Channel<Question>,
+}
+
+impl Default for TaskQuestions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskQuestions {
+    /// Creates an empty list of questions.
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            channel: Channel::new(),
+        }
+    }
+
+    /// Questions that are waiting for the answer.
+    pub fn pending(&self) -> &[Question] {
+        &self.pending
+    }
+
+    /// Questions channel's sender.
+    pub fn sender(&self) -> Sender<Question> {
+        self.channel.sender()
+    }
+
+    /// Questions channel's receiver.
+    pub fn receiver(&self) -> &Receiver<Question> {
+        self.channel.receiver()
+    }
+
+    /// Receives new questions and removes the ones that are closed.
+    pub fn receive(&mut self) {
+        self.pending.extend(self.channel.receiver().try_iter());
+        self.pending.retain(|question| !question.is_closed());
+    }
+}
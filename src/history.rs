@@ -0,0 +1,475 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(feature = "serde")]
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use crate::error::ExportError;
+use crate::{
+    metadata::TaskMetadata,
+    task::{TaskData, TaskId},
+};
+
+/// How a task finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskStatus {
+    /// The task finished its execution.
+    Completed,
+    /// The task was cancelled.
+    Cancelled,
+    /// The task panicked.
+    Failed,
+}
+
+impl TaskStatus {
+    /// Status of a finished task.
+    pub(crate) fn of(task: &TaskData) -> Self {
+        if task.is_cancelled() {
+            TaskStatus::Cancelled
+        } else if task.is_failed() {
+            TaskStatus::Failed
+        } else {
+            TaskStatus::Completed
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            TaskStatus::Completed => "Completed",
+            TaskStatus::Cancelled => "Cancelled",
+            TaskStatus::Failed => "Failed",
+        };
+        f.write_str(status)
+    }
+}
+
+/// A record of a finished task kept in the [`TaskHistory`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskRecord {
+    id: TaskId,
+    name: String,
+    collection: String,
+    metadata: TaskMetadata,
+    status: TaskStatus,
+    started_at: SystemTime,
+    finished_at: SystemTime,
+    duration: Duration,
+    error: Option<String>,
+}
+
+impl TaskRecord {
+    /// Creates a record of the finished task.
+    pub(crate) fn new(collection: &str, task: &TaskData) -> Self {
+        let duration = task.duration();
+        let finished_ago = task
+            .finished_at()
+            .map_or(Duration::ZERO, |finished_at| finished_at.elapsed());
+        let now = SystemTime::now();
+        let finished_at = now.checked_sub(finished_ago).unwrap_or(now);
+
+        Self {
+            id: task.id(),
+            name: task.name().to_owned(),
+            collection: collection.to_owned(),
+            metadata: task.metadata().clone(),
+            status: TaskStatus::of(task),
+            started_at: finished_at.checked_sub(duration).unwrap_or(finished_at),
+            finished_at,
+            duration,
+            error: task.error().map(str::to_owned),
+        }
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws a simple ui.
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if let Some(icon) = self.metadata.icon() {
+                ui.label(icon);
+            }
+            let name = ui.label(self.name.as_str());
+            if let Some(description) = self.metadata.description() {
+                name.on_hover_text(description);
+            }
+
+            let color = match self.status {
+                TaskStatus::Completed => ui.visuals().text_color(),
+                TaskStatus::Cancelled => ui.visuals().warn_fg_color,
+                TaskStatus::Failed => ui.visuals().error_fg_color,
+            };
+            ui.colored_label(color, self.status.to_string());
+            ui.weak(crate::execution::format_duration(self.duration));
+            ui.weak(self.collection.as_str());
+        });
+        self.metadata.tags_ui(ui);
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    /// Task's id.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Task's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name of the collection the task belonged to.
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// Task's metadata.
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.metadata
+    }
+
+    /// How the task finished.
+    pub fn status(&self) -> TaskStatus {
+        self.status
+    }
+
+    /// The moment when the task started its execution.
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// The moment when the task finished, was cancelled or panicked.
+    pub fn finished_at(&self) -> SystemTime {
+        self.finished_at
+    }
+
+    /// Time the task was running.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The panic message if the task failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Checks if the task's name, collection, category or one of the tags
+    /// contains `text`, ignoring case.
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        let contains = |value: &str| value.to_lowercase().contains(&text);
+
+        contains(&self.name)
+            || contains(&self.collection)
+            || self.metadata.category().is_some_and(contains)
+            || self.metadata.tags().any(contains)
+    }
+}
+
+/// Bounded history of finished tasks.
+///
+/// It keeps only the last [`capacity`](Self::capacity) records, older ones are dropped.
+/// Each collection has its own history, see [`CollectionData::history`](crate::CollectionData::history),
+/// and the manager keeps the history of all collections, see
+/// [`TaskManager::history`](crate::TaskManager::history).
+///
+/// With the `serde` feature it can be serialized and exported to JSON or CSV
+/// to be attached to a bug report.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskHistory {
+    records: VecDeque<TaskRecord>,
+    capacity: usize,
+    #[cfg(feature = "egui")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    filter: String,
+    #[cfg(feature = "egui")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    status_filter: Option<TaskStatus>,
+}
+
+impl Default for TaskHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl TaskHistory {
+    /// Default number of records that are kept.
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    /// Creates an empty history that keeps at most `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+            #[cfg(feature = "egui")]
+            filter: String::new(),
+            #[cfg(feature = "egui")]
+            status_filter: None,
+        }
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws the "Recent activity" panel with the newest records first.
+    ///
+    /// Records can be filtered by text and status.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Recent activity");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text("Filter"));
+
+            let selected = self
+                .status_filter
+                .map_or("All".to_owned(), |status| status.to_string());
+            egui::ComboBox::from_id_source(ui.id().with("task_history_status"))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.status_filter, None, "All");
+                    for status in [
+                        TaskStatus::Completed,
+                        TaskStatus::Cancelled,
+                        TaskStatus::Failed,
+                    ] {
+                        ui.selectable_value(
+                            &mut self.status_filter,
+                            Some(status),
+                            status.to_string(),
+                        );
+                    }
+                });
+
+            if ui.button("Clear").clicked() {
+                self.clear();
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                let records = self.records.iter().rev().filter(|record| {
+                    (self.status_filter.is_none() || self.status_filter == Some(record.status))
+                        && record.matches(&self.filter)
+                });
+
+                for record in records {
+                    ui.group(|ui| record.ui(ui));
+                }
+            });
+    }
+
+    /// Maximum number of records.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of records dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.records.len() > capacity {
+            self.records.pop_front();
+        }
+    }
+
+    /// Number of stored records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Checks if there's no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// An iterator visiting stored records from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &TaskRecord> {
+        self.records.iter()
+    }
+
+    /// Pushes a new record dropping the oldest one if the history is full.
+    pub fn push(&mut self, record: TaskRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// Removes all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    #[cfg(feature = "serde")]
+    /// Writes the records as a JSON array.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// let history = TaskHistory::default();
+    ///
+    /// let mut json = Vec::new();
+    /// history.write_json(&mut json).unwrap();
+    /// assert_eq!(json, b"[]");
+    /// ```
+    pub fn write_json(&self, writer: impl Write) -> Result<(), ExportError> {
+        serde_json::to_writer_pretty(writer, &self.records)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Writes the records as CSV with a header row.
+    ///
+    /// Times are written as milliseconds since the Unix epoch, durations in milliseconds
+    /// and tags are separated by `;`. User data is not exported.
+    ///
+    /// The header is written even if the history is empty.
+    pub fn write_csv(&self, writer: impl Write) -> Result<(), ExportError> {
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(writer);
+        writer.write_record(CsvRecord::HEADER)?;
+        for record in &self.records {
+            writer.serialize(CsvRecord::from(record))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Exports the records to a JSON file, see [`write_json`](Self::write_json).
+    pub fn export_json(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        self.write_json(BufWriter::new(File::create(path)?))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Exports the records to a CSV file, see [`write_csv`](Self::write_csv).
+    pub fn export_csv(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        self.write_csv(File::create(path)?)
+    }
+}
+
+/// A flat row of the CSV export.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct CsvRecord<'r> {
+    id: TaskId,
+    name: &'r str,
+    collection: &'r str,
+    status: TaskStatus,
+    started_at_ms: u128,
+    finished_at_ms: u128,
+    duration_ms: u128,
+    description: Option<&'r str>,
+    category: Option<&'r str>,
+    icon: Option<&'r str>,
+    tags: String,
+    error: Option<&'r str>,
+}
+
+#[cfg(feature = "serde")]
+impl CsvRecord<'_> {
+    /// Names of the columns, in the order of the fields.
+    const HEADER: [&'static str; 12] = [
+        "id",
+        "name",
+        "collection",
+        "status",
+        "started_at_ms",
+        "finished_at_ms",
+        "duration_ms",
+        "description",
+        "category",
+        "icon",
+        "tags",
+        "error",
+    ];
+}
+
+#[cfg(feature = "serde")]
+impl<'r> From<&'r TaskRecord> for CsvRecord<'r> {
+    fn from(record: &'r TaskRecord) -> Self {
+        let unix_ms = |time: SystemTime| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+        };
+
+        Self {
+            id: record.id,
+            name: &record.name,
+            collection: &record.collection,
+            status: record.status,
+            started_at_ms: unix_ms(record.started_at),
+            finished_at_ms: unix_ms(record.finished_at),
+            duration_ms: record.duration.as_millis(),
+            description: record.metadata.description(),
+            category: record.metadata.category(),
+            icon: record.metadata.icon(),
+            tags: record.metadata.tags().collect::<Vec<_>>().join(";"),
+            error: record.error.as_deref(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{testing::poll_until, *};
+
+    const HEADER: &str = "id,name,collection,status,started_at_ms,finished_at_ms,duration_ms,description,category,icon,tags,error";
+
+    #[test]
+    fn csv_of_an_empty_history_has_a_header() {
+        let mut csv = Vec::new();
+        TaskHistory::default().write_csv(&mut csv).unwrap();
+
+        assert_eq!(String::from_utf8(csv).unwrap(), format!("{HEADER}\n"));
+    }
+
+    #[test]
+    fn csv_has_a_row_per_record() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.add_dynamic_collection(
+            "files",
+            "Files",
+            executors::Linear::default(),
+            Handler::new(|_: ()| ()),
+        );
+        let task = Task::new("Copy", Caller::standard(async {}))
+            .with_tag("io")
+            .with_tag("disk");
+        manager.push_dynamic_task("files", task).unwrap();
+        poll_until(|| {
+            manager.process(Contexts::new());
+            manager.history().len() == 1
+        });
+
+        let mut csv = Vec::new();
+        manager.history().write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], HEADER);
+        let row: Vec<_> = lines[1].split(',').collect();
+        assert_eq!(row.len(), 12);
+        assert_eq!(row[1..4], ["Copy", "Files", "Completed"]);
+        assert_eq!(row[10], "disk;io");
+        assert_eq!(row[11], "");
+    }
+}
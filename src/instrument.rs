@@ -0,0 +1,40 @@
+use tracing::Span;
+
+use crate::{events::TaskEventKind, execution::ProgressState, task::TaskId};
+
+/// Number of progress milestones, an event is emitted each time the task
+/// completes another quarter of its work.
+const MILESTONES: f32 = 4.0;
+
+/// Creates the span the task runs in.
+pub(crate) fn task_span(collection: &str, id: TaskId, name: &str) -> Span {
+    tracing::info_span!("task", collection, task = name, id = ?id)
+}
+
+/// The last milestone reached by the task.
+pub(crate) fn milestone(state: &ProgressState) -> u8 {
+    state
+        .fraction()
+        .map_or(0, |fraction| (fraction * MILESTONES).floor() as u8)
+}
+
+/// Emits the lifecycle event inside the task's span.
+pub(crate) fn trace(span: &Span, kind: &TaskEventKind<'_>, error: Option<&str>) {
+    let _enter = span.enter();
+
+    match kind {
+        TaskEventKind::TaskQueued => tracing::debug!("Task queued"),
+        TaskEventKind::TaskStarted => tracing::debug!("Task started"),
+        TaskEventKind::Progress(state) => tracing::debug!(
+            current = state.current(),
+            total = state.total(),
+            stage = state.stage(),
+            "Task progress"
+        ),
+        TaskEventKind::TaskCompleted { duration } => {
+            tracing::info!(?duration, "Task completed")
+        }
+        TaskEventKind::TaskFailed => tracing::error!(error, "Task panicked"),
+        TaskEventKind::TaskCancelled => tracing::info!("Task cancelled"),
+    }
+}
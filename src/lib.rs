@@ -10,6 +10,14 @@
 //! The [`TaskManager`] is a core type that you must save in your app's state. Call [`TaskManager::add_collection`]
 //! to register a new collection. And then call [`TaskManager::push_task`] when you want to add a new task.
 //!
+//! Instead of calling [`TaskManager::add_collection`] every frame you can register collections once
+//! with [`TaskManager::register_collection`] and call [`TaskManager::process`] in the update function.
+//!
+//! Collections can also be defined at runtime using [`TaskManager::add_dynamic_collection`].
+//!
+//! Methods that panic if the collection has not been added have `try_` variants
+//! that return [`TaskManagerError`] instead.
+//!
 //! ## [`TasksCollection`] and [`CollectionData`]
 //!
 //! [`TasksCollection`] can be implemented for a type and then this type might be used as a type parameter
@@ -35,18 +43,54 @@
 //! ## [`TaskExecutor`]
 //!
 //! A trait that determines task's execution.
+//!
+//! ## Features
+//!
+//! - `egui` (default) - `ui` methods.
+//! - `serde` - serializable history that can be exported to JSON or CSV and persistent queues.
+//! - `tracing` - a span per task and events for its lifecycle.
+//! - `metrics` - publishes the following metrics through the [`metrics`](https://docs.rs/metrics)
+//!   facade, all of them are labeled with the `collection` name and its `key`:
+//!   - `egui_task_manager_running_tasks` and `egui_task_manager_queued_tasks` gauges.
+//!   - `egui_task_manager_tasks_completed_total`, `egui_task_manager_tasks_failed_total` and
+//!     `egui_task_manager_tasks_cancelled_total` counters.
+//!   - `egui_task_manager_task_duration_seconds` histogram, also labeled with `status`.
+//!   - `egui_task_manager_task_queue_wait_seconds` histogram.
 
-mod any;
+mod batch;
 mod channel;
 mod collection;
+mod error;
+mod events;
 mod execution;
+mod history;
+#[cfg(feature = "tracing")]
+mod instrument;
 mod manager;
+mod metadata;
+#[cfg(feature = "serde")]
+mod persistence;
+mod promise;
 mod spawning;
+mod stats;
 mod task;
+#[cfg(feature = "metrics")]
+mod telemetry;
+#[cfg(test)]
+mod testing;
 
+pub use batch::{Batch, BatchData, BatchId};
 pub use collection::*;
+pub use error::*;
+pub use events::{TaskEvent, TaskEventKind};
 pub use execution::*;
+pub use history::{TaskHistory, TaskRecord, TaskStatus};
 pub use manager::*;
+pub use metadata::TaskMetadata;
+#[cfg(feature = "serde")]
+pub use persistence::TaskSpec;
+pub use promise::Promise;
+pub use stats::CollectionStats;
 pub use task::*;
 
 /// Provides several functions and a macro to setup the runtime.
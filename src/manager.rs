@@ -1,15 +1,33 @@
 use std::{
     any::{type_name, TypeId},
     collections::HashMap,
+    sync::mpsc::Sender,
 };
 
-use crate::{any::IntoAny, TaskExecutor};
+use crate::{
+    channel::Channel, error::TaskManagerError, events::SharedSubscribers, executors::Parallel,
+    history::TaskRecord, Batch, BatchId, Handler, Promise, TaskEvent, TaskExecutor, TaskHistory,
+};
 
 use super::{
-    collection::{CollectionData, TasksCollection},
+    collection::{CollectionData, PausePolicy, ResultsHandler, TasksCollection},
     task::Task,
 };
 
+#[cfg(feature = "serde")]
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use crate::{
+    error::QueueError,
+    persistence::{TaskRegistry, TaskSpec},
+    Caller,
+};
+
 /// It holds all collections.
 ///
 /// ```rust
@@ -22,19 +40,233 @@ use super::{
 /// ```
 ///
 /// You need to call [`TaskManager::add_collection`] for all collection you want to have.
+/// Alternatively you can register collections once using [`TaskManager::register_collection`]
+/// and then call [`TaskManager::process`] every frame to handle all of them.
+///
 /// If you will try to [`TaskManager::push_task`] to a collection that has not been added the
-/// method will panic
+/// method will panic. Use [`TaskManager::try_push_task`] if you want to handle this case.
+///
+/// Collections can be registered ahead of time using [`TaskManager::register_collection`]
+/// so tasks can be pushed before the first frame.
+/// ```rust
+/// # use egui_task_manager::*;
+/// # struct Downloads;
+/// # impl<'c> TasksCollection<'c> for Downloads {
+/// #     type Context = ();
+/// #     type Target = ();
+/// #     type Executor = executors::Linear;
+/// #     fn name() -> &'static str { "Downloads" }
+/// #     fn handle(_context: Self::Context) -> Handler<'c, Self::Target> { Handler::new(|_| ()) }
+/// # }
+/// let mut manager = TaskManager::new();
+///
+/// let task = || Task::new("Download", Caller::standard(async {}));
+/// assert_eq!(
+///     manager.try_push_task::<Downloads>(task()).err(),
+///     Some(TaskManagerError::CollectionNotFound(std::any::type_name::<Downloads>()))
+/// );
+///
+/// manager.register_collection::<Downloads>();
+/// assert!(manager.try_push_task::<Downloads>(task()).is_ok());
+/// ```
 #[derive(Default)]
 pub struct TaskManager {
-    collections: HashMap<TypeId, CollectionData>,
+    collections: HashMap<CollectionKey, CollectionData>,
+    order: Vec<CollectionKey>,
+    concurrency_limit: Option<usize>,
+    fairness: Fairness,
+    next_collection: usize,
+    spawns: Channel<Spawn>,
+    subscribers: SharedSubscribers,
+    records: Channel<TaskRecord>,
+    history: TaskHistory,
+    #[cfg(feature = "serde")]
+    registry: TaskRegistry,
+}
+
+/// Decides which collection's queue gets the next free slot when the
+/// [`TaskManager`] has a concurrency limit.
+///
+/// Only applies to [`TaskManager::process`]. See [`TaskManager::set_concurrency_limit`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fairness {
+    /// Collections take turns and each of them starts one task per turn.
+    #[default]
+    RoundRobin,
+    /// Collections take turns and each of them starts up to its
+    /// [`weight`](CollectionData::weight) tasks per turn.
+    Weighted,
+}
+
+/// Key of a collection in the [`TaskManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CollectionKey {
+    /// A collection defined by a type that implements [`TasksCollection`].
+    Type(TypeId),
+    /// A collection added at runtime.
+    Dynamic(String),
+    /// The collection of the tasks pushed with [`TaskManager::spawn_promise`].
+    Promises,
+}
+
+impl CollectionKey {
+    fn of<C: 'static>() -> Self {
+        Self::Type(TypeId::of::<C>())
+    }
+}
+
+/// Pushes a task to the collection, receives `None` if the collection doesn't exist.
+type Push = Box<dyn FnOnce(Option<&mut CollectionData>) + Send>;
+
+/// A task that must be pushed to the collection with the provided key
+/// during the next [`TaskManager::process`].
+pub(crate) struct Spawn {
+    key: CollectionKey,
+    push: Push,
+}
+
+impl Spawn {
+    pub(crate) fn new<'c, C>(task: Task<C::Target>) -> Self
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        Self::with_key(CollectionKey::of::<C>(), task)
+    }
+
+    fn with_key<T: Send + 'static>(key: CollectionKey, task: Task<T>) -> Self {
+        Self {
+            key,
+            push: Box::new(move |collection| {
+                if let Some(collection) = collection {
+                    let _ = collection.push_task(task);
+                }
+            }),
+        }
+    }
+
+    /// Pushes the task created by `next` to collection `C`.
+    ///
+    /// `finish` receives `None` once the task is pushed, or the `value` back
+    /// if the collection doesn't exist.
+    pub(crate) fn chain<'c, C, R>(
+        value: R,
+        next: impl FnOnce(R) -> Task<C::Target> + Send + 'static,
+        finish: impl FnOnce(Option<R>) + Send + 'static,
+    ) -> Self
+    where
+        C: TasksCollection<'c> + 'static,
+        R: Send + 'static,
+    {
+        Self {
+            key: CollectionKey::of::<C>(),
+            push: Box::new(move |collection| match collection {
+                Some(collection) => {
+                    let _ = collection.push_task(next(value));
+                    finish(None);
+                }
+                None => finish(Some(value)),
+            }),
+        }
+    }
+
+    /// Handles the spawn as if its collection doesn't exist.
+    pub(crate) fn discard(self) {
+        (self.push)(None)
+    }
+}
+
+/// A handle that pushes tasks to the [`TaskManager`] from anywhere, e.g. from a
+/// [`Handler`] or from a running task.
+///
+/// It can be cloned and sent to other threads. Tasks are pushed to their collections
+/// during the next [`TaskManager::process`] or [`TaskManager::add_collection`]. Tasks
+/// whose collection doesn't exist at that moment are dropped.
+///
+/// ```rust
+/// # use egui_task_manager::*;
+/// # struct Pages;
+/// # impl<'c> TasksCollection<'c> for Pages {
+/// #     type Context = ();
+/// #     type Target = u32;
+/// #     type Executor = executors::Parallel;
+/// #     fn name() -> &'static str { "Pages" }
+/// #     fn handle(_context: Self::Context) -> Handler<'c, Self::Target> { Handler::new(|_| ()) }
+/// # }
+/// let mut manager = TaskManager::new();
+/// manager.register_collection::<Pages>();
+///
+/// // Every page fetches the next one until the third.
+/// fn fetch(page: u32, spawner: TaskSpawner) -> Task<u32> {
+///     Task::new(format!("Page {page}"), Caller::standard(async move {
+///         if page < 3 {
+///             spawner.spawn::<Pages>(fetch(page + 1, spawner.clone())).unwrap();
+///         }
+///         page
+///     }))
+/// }
+/// manager.push_task::<Pages>(fetch(1, manager.spawner()));
+/// ```
+#[derive(Clone)]
+pub struct TaskSpawner {
+    sender: Sender<Spawn>,
+}
+
+impl TaskSpawner {
+    /// Queues a task for collection `C`.
+    ///
+    /// Returns an error if the manager has been dropped.
+    pub fn spawn<'c, C>(&self, task: Task<C::Target>) -> Result<(), TaskManagerError>
+    where
+        C: TasksCollection<'c> + 'static,
+        C::Target: Send + 'static,
+    {
+        self.send(Spawn::new::<C>(task))
+    }
+
+    /// Queues a task for the dynamic collection with the provided key.
+    ///
+    /// Returns an error if the manager has been dropped. The task is dropped if its
+    /// return type doesn't match the collection's one.
+    pub fn spawn_dynamic<T>(
+        &self,
+        key: impl Into<String>,
+        task: Task<T>,
+    ) -> Result<(), TaskManagerError>
+    where
+        T: Send + 'static,
+    {
+        self.send(Spawn::with_key(CollectionKey::Dynamic(key.into()), task))
+    }
+
+    fn send(&self, spawn: Spawn) -> Result<(), TaskManagerError> {
+        self.sender
+            .send(spawn)
+            .map_err(|_| TaskManagerError::ManagerDropped)
+    }
 }
 
 impl TaskManager {
     #[cfg(feature = "egui")]
     /// Draws a simple ui.
+    ///
+    /// Collections are drawn in the order of [`iter_groups`](Self::iter_groups).
+    /// Grouped collections are drawn inside a collapsing header with the group's name.
     pub fn ui(&self, ui: &mut egui::Ui) {
-        for collection in self.collections.values() {
-            collection.ui(ui)
+        for (group, collections) in self.iter_groups() {
+            match group {
+                Some(group) => {
+                    ui.collapsing(group, |ui| {
+                        for collection in &collections {
+                            collection.ui(ui)
+                        }
+                    });
+                }
+                None => {
+                    for collection in collections {
+                        collection.ui(ui)
+                    }
+                }
+            }
         }
     }
 
@@ -45,37 +277,264 @@ impl TaskManager {
 
     /// Returns a reference to a [`CollectionData`] corresponding to the type
     /// parameter `C`
+    ///
+    /// # Panics
+    ///
+    /// Panics if collection `C` has not been added. See [`try_get_collection`](Self::try_get_collection).
     pub fn get_collection<'c, C>(&self) -> &CollectionData
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.try_get_collection::<C>()
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns a reference to a [`CollectionData`] corresponding to the type
+    /// parameter `C` or an error if the collection has not been added.
+    pub fn try_get_collection<'c, C>(&self) -> Result<&CollectionData, TaskManagerError>
     where
         C: TasksCollection<'c> + 'static,
     {
         self.collections
-            .get(&TypeId::of::<C>())
-            .unwrap_or_else(move || {
-                panic!(
-                    "You must add `{}` collection to the `TaskManager` by calling `add_collection`",
-                    type_name::<C>()
-                )
-            })
+            .get(&CollectionKey::of::<C>())
+            .ok_or(TaskManagerError::CollectionNotFound(type_name::<C>()))
     }
 
     /// An iterator visiting all collections.
+    ///
+    /// Collections are sorted by their [`sort_key`](CollectionData::sort_key).
+    /// Collections with the same key are kept in the registration order.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// let mut manager = TaskManager::new();
+    /// for (key, sort_key) in [("b", 0), ("c", -1), ("a", 0)] {
+    ///     manager.add_dynamic_collection(key, key, executors::Linear::default(), Handler::new(|_: ()| ()));
+    ///     manager.get_dynamic_collection_mut(key).unwrap().set_sort_key(sort_key);
+    /// }
+    ///
+    /// let names = manager.iter_collections().map(|c| c.name()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["c", "b", "a"]);
+    /// ```
     pub fn iter_collections(&self) -> impl Iterator<Item = &CollectionData> {
-        self.collections.values()
+        let mut collections = self
+            .order
+            .iter()
+            .filter_map(|key| self.collections.get(key))
+            .collect::<Vec<_>>();
+        collections.sort_by_key(|collection| collection.sort_key());
+        collections.into_iter()
+    }
+
+    /// An iterator visiting all collections grouped by their [`group`](CollectionData::group).
+    ///
+    /// Groups are ordered by the position of their first collection in
+    /// [`iter_collections`](Self::iter_collections). All collections without a group
+    /// are yielded together with `None`.
+    pub fn iter_groups(&self) -> impl Iterator<Item = (Option<&str>, Vec<&CollectionData>)> {
+        let mut groups: Vec<(Option<&str>, Vec<&CollectionData>)> = Vec::new();
+        for collection in self.iter_collections() {
+            match groups
+                .iter_mut()
+                .find(|(group, _)| *group == collection.group())
+            {
+                Some((_, collections)) => collections.push(collection),
+                None => groups.push((collection.group(), vec![collection])),
+            }
+        }
+        groups.into_iter()
+    }
+
+    /// Limits the number of tasks that can run at once across all collections.
+    /// `None` removes the limit.
+    ///
+    /// The limit is combined with the decisions of the collections' executors.
+    /// [`process`](Self::process) distributes free slots according to the [`Fairness`]
+    /// policy. [`add_collection`](Self::add_collection) ignores the policy and the
+    /// collections take free slots in the order they are added. Running tasks
+    /// are not affected if the limit is lowered.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// egui_task_manager::setup!();
+    ///
+    /// let mut manager = TaskManager::new();
+    /// manager.set_concurrency_limit(Some(2));
+    ///
+    /// for key in ["a", "b"] {
+    ///     manager.add_dynamic_collection(key, key, executors::Parallel::default(), Handler::new(|_: ()| ()));
+    ///     for _ in 0..3 {
+    ///         let task = Task::new("Wait", Caller::standard(std::future::pending::<()>()));
+    ///         manager.push_dynamic_task(key, task).unwrap();
+    ///     }
+    /// }
+    ///
+    /// manager.process(Contexts::new());
+    /// assert_eq!(manager.running_tasks(), 2);
+    /// assert_eq!(manager.get_dynamic_collection("a").unwrap().tasks().len(), 1);
+    /// assert_eq!(manager.get_dynamic_collection("b").unwrap().tasks().len(), 1);
+    /// ```
+    pub fn set_concurrency_limit(&mut self, limit: Option<usize>) -> &mut Self {
+        self.concurrency_limit = limit;
+        self
+    }
+
+    /// Maximum number of tasks that can run at once across all collections.
+    pub fn concurrency_limit(&self) -> Option<usize> {
+        self.concurrency_limit
+    }
+
+    /// Sets the policy that distributes free slots between collections.
+    pub fn set_fairness(&mut self, fairness: Fairness) -> &mut Self {
+        self.fairness = fairness;
+        self
+    }
+
+    /// The policy that distributes free slots between collections.
+    pub fn fairness(&self) -> Fairness {
+        self.fairness
+    }
+
+    /// Number of currently running tasks across all collections.
+    pub fn running_tasks(&self) -> usize {
+        self.collections
+            .values()
+            .map(|collection| collection.tasks().len())
+            .sum()
+    }
+
+    fn available_slots(&self) -> Option<usize> {
+        self.concurrency_limit
+            .map(|limit| limit.saturating_sub(self.running_tasks()))
+    }
+
+    /// Starts queued tasks of all collections within the concurrency limit.
+    fn handle_limited_execution(&mut self, mut available: usize) {
+        let count = self.order.len();
+        let mut index = self.next_collection % count.max(1);
+        let mut idle = 0;
+
+        while available > 0 && idle < count {
+            if let Some(collection) = self.collections.get_mut(&self.order[index]) {
+                let turn = match self.fairness {
+                    Fairness::RoundRobin => 1,
+                    Fairness::Weighted => collection.weight().max(1) as usize,
+                };
+
+                match collection.handle_execution_limited(turn.min(available)) {
+                    0 => idle += 1,
+                    started => {
+                        available -= started;
+                        idle = 0;
+                    }
+                }
+            }
+
+            index = (index + 1) % count;
+        }
+
+        self.next_collection = index;
+    }
+
+    fn insert_collection(&mut self, key: CollectionKey, mut collection: CollectionData) {
+        collection.set_spawns(self.spawns.sender());
+        collection.set_shared_subscribers(self.subscribers.clone());
+        collection.set_records(self.records.sender());
+        match self.collections.insert(key.clone(), collection) {
+            Some(mut replaced) => {
+                replaced.shut_down();
+                self.handle_records();
+            }
+            None => self.order.push(key),
+        }
+    }
+
+    /// Adds a listener that is called on every [`TaskEvent`] of every collection.
+    ///
+    /// See [`CollectionData::subscribe`] to listen to a single collection.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// let mut manager = TaskManager::new();
+    /// manager.subscribe(|event| {
+    ///     if let TaskEventKind::TaskCompleted { duration } = event.kind() {
+    ///         println!("{} completed in {duration:?}", event.task_name());
+    ///     }
+    /// });
+    /// ```
+    pub fn subscribe(&mut self, listener: impl FnMut(&TaskEvent<'_>) + 'static) -> &mut Self {
+        self.subscribers.push(listener);
+        self
+    }
+
+    /// History of the finished tasks of all collections.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// let manager = TaskManager::new();
+    /// for record in manager.history().iter().filter(|record| record.matches("io")) {
+    ///     println!("{} {:?} in {:?}", record.name(), record.status(), record.duration());
+    /// }
+    /// ```
+    pub fn history(&self) -> &TaskHistory {
+        &self.history
+    }
+
+    /// Mutable reference to the history of the finished tasks of all collections.
+    ///
+    /// Its [`ui`](TaskHistory::ui) can be used to show the "Recent activity" panel.
+    pub fn history_mut(&mut self) -> &mut TaskHistory {
+        &mut self.history
+    }
+
+    /// Moves the records sent by the collections to the manager's history.
+    fn handle_records(&mut self) {
+        for record in self.records.receiver().try_iter() {
+            self.history.push(record);
+        }
+    }
+
+    /// Returns a handle that can push tasks to this manager from anywhere.
+    pub fn spawner(&self) -> TaskSpawner {
+        TaskSpawner {
+            sender: self.spawns.sender(),
+        }
+    }
+
+    /// Pushes the tasks sent by [`TaskSpawner`]s and continuations to their collections.
+    ///
+    /// Tasks whose collection has not been added or doesn't accept them are dropped.
+    fn handle_spawns(&mut self) {
+        for spawn in self.spawns.receiver().try_iter() {
+            (spawn.push)(self.collections.get_mut(&spawn.key));
+        }
+    }
+
+    fn remove_collection_by_key(&mut self, key: &CollectionKey) -> Option<CollectionData> {
+        let mut collection = self.collections.remove(key)?;
+        self.order.retain(|k| k != key);
+        collection.shut_down();
+        self.handle_records();
+        Some(collection)
     }
 
     fn get_collection_mut<'c, C>(&mut self) -> &mut CollectionData
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.try_get_collection_mut::<C>()
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns a mutable reference to a [`CollectionData`] corresponding to the type
+    /// parameter `C` or an error if the collection has not been added.
+    pub fn try_get_collection_mut<'c, C>(&mut self) -> Result<&mut CollectionData, TaskManagerError>
     where
         C: TasksCollection<'c> + 'static,
     {
         self.collections
-            .get_mut(&TypeId::of::<C>())
-            .unwrap_or_else(move || {
-                panic!(
-                    "You must add `{}` collection to the `TaskManager` by calling `add_collection`",
-                    type_name::<C>()
-                )
-            })
+            .get_mut(&CollectionKey::of::<C>())
+            .ok_or(TaskManagerError::CollectionNotFound(type_name::<C>()))
     }
 
     /// Adds a new collection and handles its results.
@@ -86,41 +545,332 @@ impl TaskManager {
         C: TasksCollection<'c> + 'static,
         C::Executor: TaskExecutor + 'static,
     {
-        self.push_collection::<C>().handle_collection::<C>(context)
+        self.register_collection::<C>()
+            .handle_collection::<C>(context)
     }
 
     /// Adds a new collection. It **does not** handle the results, progression, execution and deletion.
-    /// If you use this method you **must** call [`handle_collection`](Self::handle_collection) to
-    /// see the anything.
     ///
-    /// It is recommended to use [`add_collection`](Self::add_collection).
-    fn push_collection<'c, C>(&mut self) -> &mut Self
+    /// It can be used to register a collection ahead of time, e.g. during the app's setup,
+    /// so tasks can be pushed before the first frame. You still need to call
+    /// [`add_collection`](Self::add_collection) in the update function to handle them.
+    pub fn register_collection<'c, C>(&mut self) -> &mut Self
     where
         C: TasksCollection<'c> + 'static,
         C::Executor: TaskExecutor + 'static,
     {
-        let id = TypeId::of::<C>();
-
-        if self.collections.contains_key(&id) {
-            return self;
+        let key = CollectionKey::of::<C>();
+        if !self.collections.contains_key(&key) {
+            self.insert_collection(key, CollectionData::from_collection::<C>());
         }
+        self
+    }
+
+    /// Adds a collection defined at runtime, e.g. one per open project.
+    ///
+    /// The collection is identified by `key` and displayed using `name`. Its results are
+    /// handled by `handler` in [`process`](Self::process). If a collection with the same key
+    /// already exists it will be replaced and its tasks will be cancelled.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// let mut manager = TaskManager::new();
+    ///
+    /// manager.add_dynamic_collection(
+    ///     "project-1",
+    ///     "Project 1",
+    ///     executors::Linear::default(),
+    ///     Handler::new(|value: u32| println!("Received {value}")),
+    /// );
+    ///
+    /// let task = Task::new("Build", Caller::standard(async { 42_u32 }));
+    /// assert!(manager.push_dynamic_task("project-1", task).is_ok());
+    ///
+    /// let task = Task::new("Build", Caller::standard(async { "wrong type" }));
+    /// assert!(matches!(
+    ///     manager.push_dynamic_task("project-1", task),
+    ///     Err(TaskManagerError::TargetMismatch { .. })
+    /// ));
+    ///
+    /// assert!(manager.remove_dynamic_collection("project-1").is_some());
+    /// ```
+    pub fn add_dynamic_collection<T>(
+        &mut self,
+        key: impl Into<String>,
+        name: impl Into<String>,
+        executor: impl TaskExecutor + 'static,
+        handler: Handler<'static, T>,
+    ) -> &mut Self
+    where
+        T: Send + 'static,
+    {
+        let key = key.into();
+        let mut collection = CollectionData::new::<T>(name.into(), Box::new(executor));
+        collection.set_handler(handler);
+        collection.set_key(key.clone());
 
+        self.insert_collection(CollectionKey::Dynamic(key), collection);
+        self
+    }
+
+    /// Returns a reference to the dynamic collection with the provided key.
+    pub fn get_dynamic_collection(&self, key: &str) -> Option<&CollectionData> {
+        self.collections
+            .get(&CollectionKey::Dynamic(key.to_owned()))
+    }
+
+    /// Returns a mutable reference to the dynamic collection with the provided key.
+    pub fn get_dynamic_collection_mut(&mut self, key: &str) -> Option<&mut CollectionData> {
         self.collections
-            .insert(id, CollectionData::from_collection::<C>());
+            .get_mut(&CollectionKey::Dynamic(key.to_owned()))
+    }
+
+    /// Removes the dynamic collection with the provided key and returns it.
+    ///
+    /// All tasks of the removed collection are cancelled, the running ones are
+    /// recorded in the [history](Self::history).
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use egui_task_manager::*;
+    /// egui_task_manager::setup!();
+    ///
+    /// let mut manager = TaskManager::new();
+    /// manager.add_dynamic_collection("files", "Files", executors::Linear::default(), Handler::new(|_: ()| ()));
+    ///
+    /// let task = Task::new("Copy", Caller::standard(tokio::time::sleep(Duration::from_secs(10))));
+    /// manager.push_dynamic_task("files", task).unwrap();
+    /// manager.process(Contexts::new());
+    ///
+    /// let collection = manager.remove_dynamic_collection("files").unwrap();
+    /// assert_eq!(collection.stats().cancelled(), 1);
+    ///
+    /// let record = manager.history().iter().next().unwrap();
+    /// assert_eq!(record.status(), TaskStatus::Cancelled);
+    /// ```
+    pub fn remove_dynamic_collection(&mut self, key: &str) -> Option<CollectionData> {
+        self.remove_collection_by_key(&CollectionKey::Dynamic(key.to_owned()))
+    }
+
+    /// Removes collection `C` and returns it.
+    ///
+    /// All tasks of the removed collection are cancelled, the running ones are
+    /// recorded in the [history](Self::history).
+    pub fn remove_collection<'c, C>(&mut self) -> Option<CollectionData>
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.remove_collection_by_key(&CollectionKey::of::<C>())
+    }
+
+    /// Cancels all running and queued tasks of collection `C`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if collection `C` has not been added.
+    pub fn cancel_all<'c, C>(&mut self)
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.get_collection_mut::<C>().cancel_all()
+    }
+
+    /// Pauses collection `C`. See [`CollectionData::pause`].
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// # struct Uploads;
+    /// # impl<'c> TasksCollection<'c> for Uploads {
+    /// #     type Context = ();
+    /// #     type Target = ();
+    /// #     type Executor = executors::Parallel;
+    /// #     fn name() -> &'static str { "Uploads" }
+    /// #     fn handle(_context: Self::Context) -> Handler<'c, Self::Target> { Handler::new(|_| ()) }
+    /// # }
+    /// let mut manager = TaskManager::new();
+    /// manager.register_collection::<Uploads>();
+    ///
+    /// manager.pause::<Uploads>(PausePolicy::Suspend);
+    /// manager.push_task::<Uploads>(Task::new("Upload", Caller::standard(async {})));
+    ///
+    /// // The task is not started while the collection is paused.
+    /// manager.process(Contexts::new());
+    /// let uploads = manager.get_collection::<Uploads>();
+    /// assert_eq!(uploads.executor().iter_tasks().count(), 1);
+    /// assert!(uploads.tasks().is_empty());
+    ///
+    /// manager.cancel_all::<Uploads>();
+    /// assert_eq!(manager.get_collection::<Uploads>().executor().iter_tasks().count(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if collection `C` has not been added.
+    pub fn pause<'c, C>(&mut self, policy: PausePolicy)
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.get_collection_mut::<C>().pause(policy)
+    }
+
+    /// Resumes collection `C`. See [`CollectionData::resume`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if collection `C` has not been added.
+    pub fn resume<'c, C>(&mut self)
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.get_collection_mut::<C>().resume()
+    }
+
+    /// Pushes a task to the executor of the dynamic collection with the provided key.
+    ///
+    /// Returns an error if there's no such collection or its tasks return another type.
+    pub fn push_dynamic_task<T>(&mut self, key: &str, task: Task<T>) -> Result<(), TaskManagerError>
+    where
+        T: Send + 'static,
+    {
+        let collection = self
+            .collections
+            .get_mut(&CollectionKey::Dynamic(key.to_owned()))
+            .ok_or_else(|| TaskManagerError::DynamicCollectionNotFound(key.to_owned()))?;
+
+        collection.push_task(task)
+    }
+
+    /// Pushes a task whose result is stored in the returned [`Promise`] instead of
+    /// being passed to a [`Handler`].
+    ///
+    /// The task is executed in the "Promises" collection which is added on the first call.
+    /// It's shown in the manager's ui and can be cancelled like any other task, in which
+    /// case the promise is [cancelled](Promise::is_cancelled). The task's continuation
+    /// set with [`Task::then`] is replaced. Promises are handled by [`process`](Self::process).
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// egui_task_manager::setup!();
+    ///
+    /// let mut manager = TaskManager::new();
+    /// let mut promise = manager.spawn_promise(Task::new("Load", Caller::standard(async { 42 })));
+    ///
+    /// // Every frame:
+    /// manager.process(Contexts::new());
+    /// if let Some(value) = promise.ready() {
+    ///     println!("Loaded {value}");
+    /// }
+    /// ```
+    pub fn spawn_promise<T>(&mut self, task: Task<T>) -> Promise<T>
+    where
+        T: Send + 'static,
+    {
+        if !self.collections.contains_key(&CollectionKey::Promises) {
+            let collection = CollectionData::new::<()>("Promises", Box::<Parallel>::default());
+            self.insert_collection(CollectionKey::Promises, collection);
+        }
+
+        let (task, promise) = task.into_promise();
+        if let Some(collection) = self.collections.get_mut(&CollectionKey::Promises) {
+            collection.push_any_task(task);
+        }
+        promise
+    }
+
+    /// Adds a new collection with a context that is used to handle its results.
+    ///
+    /// Since the context is stored in the manager it must not borrow anything. Results
+    /// of this collection are handled by [`process`](Self::process) even if the context
+    /// is not provided there.
+    pub fn register_collection_with<C>(&mut self, context: C::Context) -> &mut Self
+    where
+        C: TasksCollection<'static> + 'static,
+        C::Executor: TaskExecutor + 'static,
+    {
+        let handle = C::handle(context);
+
+        self.register_collection::<C>()
+            .get_collection_mut::<C>()
+            .set_handler(handle);
         self
     }
 
+    /// Handles all registered collections.
+    ///
+    /// It must be called in the beginning of the update function. Collections are handled
+    /// using the contexts provided in `contexts`. Results of the collections without
+    /// a context are handled by the handler registered with
+    /// [`register_collection_with`](Self::register_collection_with) or kept until
+    /// a context is provided.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// struct Counter;
+    ///
+    /// impl<'c> TasksCollection<'c> for Counter {
+    ///     type Context = &'c mut u32;
+    ///     type Target = u32;
+    ///     type Executor = executors::Parallel;
+    ///
+    ///     fn name() -> &'static str {
+    ///         "Counter"
+    ///     }
+    ///
+    ///     fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+    ///         Handler::new(|value| *context += value)
+    ///     }
+    /// }
+    ///
+    /// // During the setup.
+    /// let mut manager = TaskManager::new();
+    /// manager.register_collection::<Counter>();
+    ///
+    /// // In the update function.
+    /// let mut counter = 0;
+    /// manager.process(Contexts::new().with::<Counter>(&mut counter));
+    /// ```
+    pub fn process(&mut self, mut contexts: Contexts<'_>) {
+        self.handle_spawns();
+        let limited = self.concurrency_limit.is_some();
+
+        for key in &self.order {
+            let Some(collection) = self.collections.get_mut(key) else {
+                continue;
+            };
+
+            let handle = match key {
+                CollectionKey::Type(id) => contexts.handlers.remove(id),
+                CollectionKey::Dynamic(_) | CollectionKey::Promises => None,
+            };
+
+            if !limited {
+                collection.handle_execution();
+            }
+            collection.process_running(handle);
+        }
+
+        if let Some(available) = self.available_slots() {
+            self.handle_limited_execution(available);
+        }
+        self.handle_records();
+    }
+
     /// Handles the tasks of the specified collection. It **does not** add a new collection
-    /// to the manager. If you want to use this method you **must** call [`push_collection`](Self::push_collection).
+    /// to the manager. If you want to use this method you **must** call [`register_collection`](Self::register_collection).
     ///
     /// It is recommended to use [`add_collection`](Self::add_collection).
     fn handle_collection<'c, C>(&mut self, context: C::Context) -> &mut Self
     where
         C: TasksCollection<'c> + 'static,
     {
-        let handle = C::handle(context).into_any();
+        self.handle_spawns();
+        let handle = CollectionData::results_handler(C::handle(context));
+        let available = self.available_slots();
+        let collection = self.get_collection_mut::<C>();
 
-        self.get_collection_mut::<C>().handle_all(handle);
+        collection.handle_execution_limited(available.unwrap_or(usize::MAX));
+        collection.process_running(Some(handle));
+        self.handle_records();
         self
     }
 
@@ -134,6 +884,331 @@ impl TaskManager {
         C: TasksCollection<'c> + 'static,
         C::Target: Send + 'static,
     {
-        self.get_collection_mut::<C>().push_task(task.into_any());
+        self.try_push_task::<C>(task)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Pushes a batch of tasks to the executor of the specified collection.
+    ///
+    /// See [`Batch`] for more information.
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// # struct Imports;
+    /// # impl<'c> TasksCollection<'c> for Imports {
+    /// #     type Context = ();
+    /// #     type Target = u32;
+    /// #     type Executor = executors::Parallel;
+    /// #     fn name() -> &'static str { "Imports" }
+    /// #     fn handle(_context: Self::Context) -> Handler<'c, Self::Target> { Handler::new(|_| ()) }
+    /// # }
+    /// let mut manager = TaskManager::new();
+    /// manager.register_collection::<Imports>();
+    ///
+    /// let batch = Batch::new("Import 3 files")
+    ///     .with_tasks((1..=3).map(|i| Task::new(format!("File {i}"), Caller::standard(async move { i }))))
+    ///     .on_complete(|results| println!("Imported {} files", results.len()));
+    /// let id = manager.push_batch::<Imports>(batch);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if collection `C` has not been added via [`TaskManager::add_collection`].
+    pub fn push_batch<'c, C>(&mut self, batch: Batch<C::Target>) -> BatchId
+    where
+        C: TasksCollection<'c> + 'static,
+        C::Target: Send + 'static,
+    {
+        self.try_push_batch::<C>(batch)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Pushes a batch of tasks to the executor of the specified collection or returns
+    /// an error if collection `C` has not been added.
+    pub fn try_push_batch<'c, C>(
+        &mut self,
+        batch: Batch<C::Target>,
+    ) -> Result<BatchId, TaskManagerError>
+    where
+        C: TasksCollection<'c> + 'static,
+        C::Target: Send + 'static,
+    {
+        self.try_get_collection_mut::<C>()?.push_batch(batch)
+    }
+
+    /// Pushes a task to the executor of the specified collection or returns an error
+    /// if collection `C` has not been added.
+    pub fn try_push_task<'c, C>(&mut self, task: Task<C::Target>) -> Result<(), TaskManagerError>
+    where
+        C: TasksCollection<'c> + 'static,
+        C::Target: Send + 'static,
+    {
+        self.try_get_collection_mut::<C>()?.push_task(task)
+    }
+}
+
+/// Persistent queues.
+///
+/// Tasks pushed with [`push_spec`](TaskManager::push_spec) remember their [`TaskSpec`].
+/// The specs of the running and queued tasks can be saved with
+/// [`save_queue`](TaskManager::save_queue) when the app is closed and pushed again
+/// on the next launch.
+///
+/// ```rust
+/// # use egui_task_manager::*;
+/// egui_task_manager::setup!();
+///
+/// let mut manager = TaskManager::new();
+/// manager
+///     .add_dynamic_collection("uploads", "Uploads", executors::Linear::default(), Handler::new(|_: ()| ()))
+///     .register_task_kind("upload", |path: String| {
+///         Caller::standard(async move { println!("Uploaded {path}") })
+///     });
+///
+/// let spec = TaskSpec::new("uploads", "upload", "Upload a.png", "a.png").unwrap();
+/// manager.push_spec(spec).unwrap();
+///
+/// // When the app is closed:
+/// let path = std::env::temp_dir().join("egui_task_manager_uploads.json");
+/// manager.save_queue(&path).unwrap();
+///
+/// // On the next launch:
+/// for spec in TaskManager::load_queue(&path).unwrap() {
+///     manager.push_spec(spec).unwrap();
+/// }
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+#[cfg(feature = "serde")]
+impl TaskManager {
+    /// Registers a builder that creates the [`Caller`] of the tasks of `kind` from
+    /// the parameters of their [`TaskSpec`]s.
+    ///
+    /// The previous builder of this kind is replaced.
+    pub fn register_task_kind<T, P, F>(&mut self, kind: impl Into<String>, build: F) -> &mut Self
+    where
+        T: Send + 'static,
+        P: serde::de::DeserializeOwned,
+        F: Fn(P) -> Caller<T> + 'static,
+    {
+        self.registry.register(kind.into(), build);
+        self
+    }
+
+    /// Creates the task described by `spec` and pushes it to the collection with the spec's
+    /// [collection](TaskSpec::collection) key.
+    ///
+    /// The key is looked up among the dynamic collections first, then among the
+    /// [`TasksCollection::key`]s of the registered collections.
+    pub fn push_spec(&mut self, spec: TaskSpec) -> Result<(), QueueError> {
+        let key = spec.collection();
+        let dynamic = CollectionKey::Dynamic(key.to_owned());
+        let collection = match self.collections.contains_key(&dynamic) {
+            true => self.collections.get_mut(&dynamic),
+            false => self
+                .collections
+                .iter_mut()
+                .find(|(k, collection)| {
+                    matches!(k, CollectionKey::Type(_)) && collection.key() == key
+                })
+                .map(|(_, collection)| collection),
+        }
+        .ok_or_else(|| TaskManagerError::KeyNotFound(key.to_owned()))?;
+
+        self.registry.push(&spec, collection)
+    }
+
+    /// Specs of the running and queued tasks of all collections.
+    ///
+    /// Running tasks are included since they're aborted when the app is closed,
+    /// they will be started from the beginning once their specs are pushed again.
+    pub fn task_specs(&self) -> Vec<TaskSpec> {
+        self.order
+            .iter()
+            .filter_map(|key| self.collections.get(key))
+            .flat_map(CollectionData::task_specs)
+            .cloned()
+            .collect()
+    }
+
+    /// Saves the [specs](Self::task_specs) of the running and queued tasks to a JSON file.
+    pub fn save_queue(&self, path: impl AsRef<Path>) -> Result<(), QueueError> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &self.task_specs())?;
+        Ok(())
+    }
+
+    /// Loads the specs saved by [`save_queue`](Self::save_queue).
+    ///
+    /// Use [`push_spec`](Self::push_spec) to push them.
+    pub fn load_queue(path: impl AsRef<Path>) -> Result<Vec<TaskSpec>, QueueError> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Contexts of the collections that are passed to [`TaskManager::process`].
+#[derive(Default)]
+pub struct Contexts<'c> {
+    handlers: HashMap<TypeId, ResultsHandler<'c>>,
+}
+
+impl<'c> Contexts<'c> {
+    /// Creates an empty map of contexts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the context of collection `C`.
+    pub fn with<C>(mut self, context: C::Context) -> Self
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        self.insert::<C>(context);
+        self
+    }
+
+    /// Adds the context of collection `C`.
+    pub fn insert<C>(&mut self, context: C::Context) -> &mut Self
+    where
+        C: TasksCollection<'c> + 'static,
+    {
+        let handle = CollectionData::results_handler(C::handle(context));
+        self.handlers.insert(TypeId::of::<C>(), handle);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use crate::{testing::poll_until, *};
+
+    struct Pages;
+
+    impl<'c> TasksCollection<'c> for Pages {
+        type Context = &'c mut Vec<u32>;
+        type Target = u32;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Pages"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|page| context.push(page))
+        }
+    }
+
+    fn fetch(page: u32, spawner: TaskSpawner) -> Task<u32> {
+        Task::new(
+            format!("Page {page}"),
+            Caller::standard(async move {
+                if page < 3 {
+                    spawner
+                        .spawn::<Pages>(fetch(page + 1, spawner.clone()))
+                        .unwrap();
+                }
+                page
+            }),
+        )
+    }
+
+    #[test]
+    fn running_tasks_push_tasks_with_the_spawner() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Pages>();
+        manager.push_task::<Pages>(fetch(1, manager.spawner()));
+
+        let mut pages = Vec::new();
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Pages>(&mut pages));
+            pages.len() == 3
+        });
+
+        pages.sort();
+        assert_eq!(pages, [1, 2, 3]);
+    }
+
+    #[test]
+    fn promises_receive_the_results() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        let mut promise = manager.spawn_promise(Task::new("Load", Caller::standard(async { 42 })));
+        poll_until(|| {
+            manager.process(Contexts::new());
+            promise.ready().is_some()
+        });
+
+        assert_eq!(promise.ready(), Some(&42));
+        assert!(!promise.is_cancelled());
+    }
+
+    #[test]
+    fn finished_tasks_are_recorded() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.add_dynamic_collection(
+            "files",
+            "Files",
+            executors::Linear::default(),
+            Handler::new(|_: ()| ()),
+        );
+
+        let task = Task::new("Copy", Caller::standard(async {})).with_tag("io");
+        manager.push_dynamic_task("files", task).unwrap();
+        poll_until(|| {
+            manager.process(Contexts::new());
+            manager.history().len() == 1
+        });
+
+        let record = manager.history().iter().next().unwrap();
+        assert_eq!(record.name(), "Copy");
+        assert_eq!(record.status(), TaskStatus::Completed);
+        assert!(record.duration() < Duration::from_millis(200));
+        assert!(record.matches("IO"));
+    }
+
+    #[test]
+    fn subscribers_receive_the_events_of_all_collections() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.add_dynamic_collection(
+            "files",
+            "Files",
+            executors::Linear::default(),
+            Handler::new(|_: ()| ()),
+        );
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let cloned = events.clone();
+        manager.subscribe(move |event| {
+            let kind = match event.kind() {
+                TaskEventKind::TaskQueued => "queued",
+                TaskEventKind::TaskStarted => "started",
+                TaskEventKind::TaskCompleted { .. } => "completed",
+                _ => return,
+            };
+            cloned
+                .borrow_mut()
+                .push(format!("{} {kind}", event.task_name()));
+        });
+
+        let task = Task::new("Copy", Caller::standard(async {}));
+        manager.push_dynamic_task("files", task).unwrap();
+        poll_until(|| {
+            manager.process(Contexts::new());
+            events.borrow().len() == 3
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            ["Copy queued", "Copy started", "Copy completed"]
+        );
     }
 }
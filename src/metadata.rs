@@ -0,0 +1,133 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeSet, HashMap},
+    fmt,
+    sync::Arc,
+};
+
+/// Optional information about a task that is displayed in the ui and can be used
+/// to find the task, e.g. to filter tasks by tag.
+///
+/// ```rust
+/// # use egui_task_manager::*;
+/// struct Document(u32);
+///
+/// let task = Task::new("Save", Caller::standard(async {}))
+///     .with_description("Saves the document to the disk")
+///     .with_tags(["io", "documents"])
+///     .with_icon("💾")
+///     .with_user_data(Document(7));
+///
+/// let metadata = task.metadata();
+/// assert!(metadata.has_tag("io"));
+/// assert_eq!(metadata.user_data::<Document>().map(|doc| doc.0), Some(7));
+/// ```
+///
+/// With the `serde` feature it can be serialized, the user data is skipped.
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskMetadata {
+    description: Option<String>,
+    tags: BTreeSet<String>,
+    icon: Option<String>,
+    category: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    user_data: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for TaskMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskMetadata")
+            .field("description", &self.description)
+            .field("tags", &self.tags)
+            .field("icon", &self.icon)
+            .field("category", &self.category)
+            .field("user_data", &self.user_data.len())
+            .finish()
+    }
+}
+
+impl TaskMetadata {
+    /// Task's description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Sets task's description.
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.description = Some(description.into());
+    }
+
+    /// Task's tags in alphabetical order.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(String::as_str)
+    }
+
+    /// Checks if the task has the tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Adds a tag to the task.
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        self.tags.insert(tag.into());
+    }
+
+    /// Removes a tag from the task.
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.remove(tag);
+    }
+
+    /// Task's icon, e.g. an emoji.
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// Sets task's icon.
+    pub fn set_icon(&mut self, icon: impl Into<String>) {
+        self.icon = Some(icon.into());
+    }
+
+    /// Task's category.
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// Sets task's category.
+    pub fn set_category(&mut self, category: impl Into<String>) {
+        self.category = Some(category.into());
+    }
+
+    /// Returns the user data of type `T` if it was set.
+    pub fn user_data<T: Any>(&self) -> Option<&T> {
+        self.user_data
+            .get(&TypeId::of::<T>())
+            .and_then(|data| data.downcast_ref())
+    }
+
+    /// Sets the user data of type `T`. The previous value of this type is replaced.
+    pub fn insert_user_data<T: Any + Send + Sync>(&mut self, data: T) {
+        self.user_data.insert(TypeId::of::<T>(), Arc::new(data));
+    }
+
+    /// Removes the user data of type `T`.
+    pub fn remove_user_data<T: Any>(&mut self) {
+        self.user_data.remove(&TypeId::of::<T>());
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws the tags as chips.
+    pub(crate) fn tags_ui(&self, ui: &mut egui::Ui) {
+        if self.tags.is_empty() {
+            return;
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for tag in &self.tags {
+                egui::Frame::group(ui.style())
+                    .inner_margin(2.0)
+                    .show(ui, |ui| ui.small(tag));
+            }
+        });
+    }
+}
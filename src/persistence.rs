@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    collection::CollectionData, error::QueueError, execution::Caller, metadata::TaskMetadata,
+    task::Task,
+};
+
+/// Serializable description of a task that can be saved and pushed again later,
+/// e.g. after the app is restarted.
+///
+/// The task's [`Caller`] is rebuilt from the spec's kind and parameters by the builder
+/// registered with [`TaskManager::register_task_kind`](crate::TaskManager::register_task_kind).
+///
+/// The spec refers to its collection by the key of a dynamic collection or by
+/// [`TasksCollection::key`](crate::TasksCollection::key).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSpec {
+    collection: String,
+    kind: String,
+    name: String,
+    params: serde_json::Value,
+    #[serde(default)]
+    metadata: TaskMetadata,
+}
+
+impl TaskSpec {
+    /// Creates a spec of a task of `kind` that will be pushed to the collection
+    /// with the `collection` key.
+    ///
+    /// Returns an error if `params` can't be serialized.
+    pub fn new(
+        collection: impl Into<String>,
+        kind: impl Into<String>,
+        name: impl Into<String>,
+        params: impl Serialize,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            collection: collection.into(),
+            kind: kind.into(),
+            name: name.into(),
+            params: serde_json::to_value(params)?,
+            metadata: TaskMetadata::default(),
+        })
+    }
+
+    /// Sets the metadata of the task. The user data is not saved.
+    pub fn with_metadata(mut self, metadata: TaskMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Key of the collection the task is pushed to.
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// Kind of the task that determines which builder creates its [`Caller`].
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Task's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Parameters that are passed to the builder.
+    pub fn params(&self) -> &serde_json::Value {
+        &self.params
+    }
+
+    /// Task's metadata.
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.metadata
+    }
+}
+
+type Builder = Box<dyn Fn(&TaskSpec, &mut CollectionData) -> Result<(), QueueError>>;
+
+/// Builders that create tasks from [`TaskSpec`]s.
+#[derive(Default)]
+pub(crate) struct TaskRegistry {
+    builders: HashMap<String, Builder>,
+}
+
+impl TaskRegistry {
+    pub(crate) fn register<T, P, F>(&mut self, kind: String, build: F)
+    where
+        T: Send + 'static,
+        P: DeserializeOwned,
+        F: Fn(P) -> Caller<T> + 'static,
+    {
+        let builder: Builder = Box::new(move |spec, collection| {
+            let params = serde_json::from_value(spec.params.clone())?;
+            let mut task = Task::new(spec.name.clone(), (build)(params)).with_spec(spec.clone());
+            *task.metadata_mut() = spec.metadata.clone();
+
+            collection.push_task(task)?;
+            Ok(())
+        });
+        self.builders.insert(kind, builder);
+    }
+
+    /// Creates the task described by `spec` and pushes it to `collection`.
+    pub(crate) fn push(
+        &self,
+        spec: &TaskSpec,
+        collection: &mut CollectionData,
+    ) -> Result<(), QueueError> {
+        let build = self
+            .builders
+            .get(&spec.kind)
+            .ok_or_else(|| QueueError::UnknownKind(spec.kind.clone()))?;
+
+        (build)(spec, collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::poll_until, *};
+
+    struct Uploads;
+
+    impl<'c> TasksCollection<'c> for Uploads {
+        type Context = &'c mut Vec<String>;
+        type Target = String;
+        type Executor = executors::Linear;
+
+        fn name() -> &'static str {
+            "Uploads"
+        }
+
+        fn key() -> &'static str {
+            "uploads"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|path| context.push(path))
+        }
+    }
+
+    fn uploads_manager() -> TaskManager {
+        let mut manager = TaskManager::new();
+        manager
+            .register_collection::<Uploads>()
+            .register_task_kind("upload", |path: String| {
+                Caller::standard(async move { path })
+            });
+        manager
+    }
+
+    #[test]
+    fn specs_of_typed_collections_are_restored() {
+        crate::setup!();
+
+        let mut manager = uploads_manager();
+        for path in ["a.png", "b.png"] {
+            let spec = TaskSpec::new(Uploads::key(), "upload", path, path).unwrap();
+            manager.push_spec(spec).unwrap();
+        }
+
+        let json = serde_json::to_string(&manager.task_specs()).unwrap();
+        let specs: Vec<TaskSpec> = serde_json::from_str(&json).unwrap();
+        assert_eq!(specs.len(), 2);
+
+        let mut manager = uploads_manager();
+        for spec in specs {
+            manager.push_spec(spec).unwrap();
+        }
+
+        let mut uploaded = Vec::new();
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Uploads>(&mut uploaded));
+            uploaded.len() == 2
+        });
+        assert_eq!(uploaded, ["a.png", "b.png"]);
+    }
+
+    #[test]
+    fn saved_queues_are_loaded() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager
+            .add_dynamic_collection(
+                "uploads",
+                "Uploads",
+                executors::Linear::default(),
+                Handler::new(|_: ()| ()),
+            )
+            .register_task_kind("upload", |_: String| Caller::standard(async {}));
+        for path in ["a.png", "b.png"] {
+            let spec = TaskSpec::new("uploads", "upload", path, path).unwrap();
+            manager.push_spec(spec).unwrap();
+        }
+
+        let path = std::env::temp_dir().join("egui_task_manager_saved_queue.json");
+        manager.save_queue(&path).unwrap();
+        let specs = TaskManager::load_queue(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(specs.len(), 2);
+        for spec in specs {
+            manager.push_spec(spec).unwrap();
+        }
+        poll_until(|| {
+            manager.process(Contexts::new());
+            manager.history().len() == 4
+        });
+    }
+
+    #[test]
+    fn unknown_collection_key() {
+        let mut manager = uploads_manager();
+        let spec = TaskSpec::new("Uploads", "upload", "a.png", "a.png").unwrap();
+
+        assert!(matches!(
+            manager.push_spec(spec),
+            Err(QueueError::Manager(TaskManagerError::KeyNotFound(key))) if key == "Uploads"
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// A result slot of a task pushed with [`TaskManager::spawn_promise`](crate::TaskManager::spawn_promise).
+///
+/// Keep it in the widget's state and poll it every frame.
+pub struct Promise<T> {
+    receiver: oneshot::Receiver<T>,
+    state: PromiseState<T>,
+}
+
+enum PromiseState<T> {
+    Pending,
+    Ready(T),
+    Cancelled,
+}
+
+impl<T> Promise<T> {
+    pub(crate) fn new(receiver: oneshot::Receiver<T>) -> Self {
+        Self {
+            receiver,
+            state: PromiseState::Pending,
+        }
+    }
+
+    fn poll(&mut self) {
+        if let PromiseState::Pending = self.state {
+            match self.receiver.try_recv() {
+                Ok(value) => self.state = PromiseState::Ready(value),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => self.state = PromiseState::Cancelled,
+            }
+        }
+    }
+
+    /// Returns the task's result if it's ready.
+    pub fn ready(&mut self) -> Option<&T> {
+        self.poll();
+        match &self.state {
+            PromiseState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the task's result if it's ready.
+    pub fn ready_mut(&mut self) -> Option<&mut T> {
+        self.poll();
+        match &mut self.state {
+            PromiseState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Checks if the task was cancelled or dropped before producing the result.
+    pub fn is_cancelled(&mut self) -> bool {
+        self.poll();
+        matches!(self.state, PromiseState::Cancelled)
+    }
+}
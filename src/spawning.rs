@@ -1,4 +1,14 @@
-use std::future::Future;
+use std::{
+    any::Any,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
 
 /// The handle that allows the task to be aborted
 pub struct TaskHandle(tokio::task::JoinHandle<()>);
@@ -18,3 +28,97 @@ impl TaskHandle {
         self.0.abort()
     }
 }
+
+/// Suspends the futures wrapped by it while it's paused.
+#[derive(Clone, Default)]
+pub(crate) struct PauseGate(Arc<PauseGateInner>);
+
+#[derive(Default)]
+struct PauseGateInner {
+    paused: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl PauseGate {
+    pub(crate) fn set_paused(&self, paused: bool) {
+        self.0.paused.store(paused, Ordering::SeqCst);
+
+        if !paused {
+            if let Ok(mut wakers) = self.0.wakers.lock() {
+                wakers.drain(..).for_each(Waker::wake);
+            }
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::SeqCst)
+    }
+
+    fn register(&self, waker: &Waker) {
+        if let Ok(mut wakers) = self.0.wakers.lock() {
+            if !wakers.iter().any(|w| w.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+        }
+    }
+
+    pub(crate) fn wrap<F: Future + Unpin>(&self, fut: F) -> Pausable<F> {
+        Pausable {
+            gate: self.clone(),
+            inner: fut,
+        }
+    }
+}
+
+/// A future that is not polled while its [`PauseGate`] is paused.
+pub(crate) struct Pausable<F> {
+    gate: PauseGate,
+    inner: F,
+}
+
+impl<F: Future + Unpin> Future for Pausable<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.gate.is_paused() {
+            self.gate.register(cx.waker());
+            // The gate might have been resumed before the waker was registered.
+            if self.gate.is_paused() {
+                return Poll::Pending;
+            }
+        }
+
+        Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+/// A future that catches the panics of the inner future.
+pub(crate) struct CatchUnwind<F>(F);
+
+impl<F: Future + Unpin> CatchUnwind<F> {
+    pub(crate) fn new(fut: F) -> Self {
+        Self(fut)
+    }
+}
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.0;
+        match panic::catch_unwind(AssertUnwindSafe(|| Pin::new(inner).poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(panic_message(payload.as_ref()))),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "The task panicked".to_owned()
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::VecDeque, time::Duration};
+
+/// Counters and timings of the tasks of a collection.
+///
+/// See [`CollectionData::stats`](crate::CollectionData::stats).
+#[derive(Debug, Clone, Default)]
+pub struct CollectionStats {
+    queued: u64,
+    started: u64,
+    completed: u64,
+    failed: u64,
+    cancelled: u64,
+    queue_depth: usize,
+    total_duration: Duration,
+    recent_durations: VecDeque<Duration>,
+    total_wait: Duration,
+}
+
+impl CollectionStats {
+    /// Number of the last completed tasks whose durations are used for
+    /// [`p95_duration`](Self::p95_duration).
+    pub const DURATION_SAMPLES: usize = 100;
+
+    pub(crate) fn record_queued(&mut self) {
+        self.queued += 1;
+        self.queue_depth += 1;
+    }
+
+    pub(crate) fn record_started(&mut self, wait: Duration) {
+        self.started += 1;
+        self.queue_depth = self.queue_depth.saturating_sub(1);
+        self.total_wait += wait;
+    }
+
+    pub(crate) fn record_completed(&mut self, duration: Duration) {
+        self.completed += 1;
+        self.total_duration += duration;
+
+        if self.recent_durations.len() == Self::DURATION_SAMPLES {
+            self.recent_durations.pop_front();
+        }
+        self.recent_durations.push_back(duration);
+    }
+
+    pub(crate) fn record_failed(&mut self) {
+        self.failed += 1;
+    }
+
+    /// Records a cancelled task that was running.
+    pub(crate) fn record_cancelled(&mut self) {
+        self.cancelled += 1;
+    }
+
+    /// Records a cancelled task that was waiting in the queue.
+    pub(crate) fn record_dequeued(&mut self) {
+        self.cancelled += 1;
+        self.queue_depth = self.queue_depth.saturating_sub(1);
+    }
+
+    #[cfg(feature = "egui")]
+    /// Draws a simple ui.
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        use crate::execution::format_duration;
+
+        let format = |duration: Option<Duration>| duration.map_or("-".to_owned(), format_duration);
+
+        egui::Grid::new(ui.id().with("collection_stats"))
+            .num_columns(2)
+            .show(ui, |ui| {
+                let rows = [
+                    ("Queued", self.queued.to_string()),
+                    ("Started", self.started.to_string()),
+                    ("Completed", self.completed.to_string()),
+                    ("Failed", self.failed.to_string()),
+                    ("Cancelled", self.cancelled.to_string()),
+                    ("Queue depth", self.queue_depth.to_string()),
+                    ("Average duration", format(self.average_duration())),
+                    ("p95 duration", format(self.p95_duration())),
+                    ("Average wait", format(self.average_wait())),
+                ];
+
+                for (label, value) in rows {
+                    ui.weak(label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Total number of tasks pushed to the collection.
+    pub fn queued(&self) -> u64 {
+        self.queued
+    }
+
+    /// Total number of tasks that started their execution.
+    pub fn started(&self) -> u64 {
+        self.started
+    }
+
+    /// Total number of tasks that finished their execution.
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+
+    /// Total number of tasks that panicked.
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+
+    /// Total number of tasks that were cancelled while running or waiting in the queue.
+    pub fn cancelled(&self) -> u64 {
+        self.cancelled
+    }
+
+    /// Number of tasks that are currently waiting in the queue.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth
+    }
+
+    /// Average duration of the completed tasks.
+    pub fn average_duration(&self) -> Option<Duration> {
+        average(self.total_duration, self.completed)
+    }
+
+    /// 95th percentile of the durations of the last
+    /// [`DURATION_SAMPLES`](Self::DURATION_SAMPLES) completed tasks.
+    pub fn p95_duration(&self) -> Option<Duration> {
+        let mut durations = self.recent_durations.iter().copied().collect::<Vec<_>>();
+        durations.sort_unstable();
+
+        let index = (durations.len() * 95).div_ceil(100).checked_sub(1)?;
+        durations.get(index).copied()
+    }
+
+    /// Total time the started tasks spent waiting in the queue.
+    pub fn total_wait(&self) -> Duration {
+        self.total_wait
+    }
+
+    /// Average time the started tasks spent waiting in the queue.
+    pub fn average_wait(&self) -> Option<Duration> {
+        average(self.total_wait, self.started)
+    }
+}
+
+fn average(total: Duration, count: u64) -> Option<Duration> {
+    (count > 0).then(|| total.div_f64(count as f64))
+}
@@ -1,99 +1,527 @@
 use std::{
     any::Any,
-    sync::{mpsc::Sender, Arc, OnceLock},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{SendError, Sender},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
 };
 
+use tokio::sync::oneshot;
+
 use crate::{
-    any::{HigherKinded, IntoAny},
-    execution::{Caller, Finished, TaskProgress},
-    spawning::TaskHandle,
+    batch::{BatchId, BatchSlot},
+    collection::TasksCollection,
+    execution::{Caller, Finished, Question, TaskLog, TaskProgress, TaskQuestions},
+    manager::Spawn,
+    metadata::TaskMetadata,
+    promise::Promise,
+    spawning::{CatchUnwind, PauseGate, TaskHandle},
 };
 
-/// Task that has `Box<dyn Any + Send>` as a return type.
-pub type AnyTask = Task<Box<dyn Any + Send>>;
+#[cfg(feature = "serde")]
+use crate::persistence::TaskSpec;
+#[cfg(feature = "tracing")]
+use crate::{events::TaskEventKind, instrument};
+
+/// A task that sends its result to the collection it was pushed to.
+///
+/// Tasks are converted to it when they are pushed to a collection.
+pub type AnyTask = Task<()>;
+
+/// Unique identifier of a [`Task`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskId(u64);
+
+impl TaskId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 /// A task that can be executed.
 pub struct Task<R> {
+    id: TaskId,
     name: String,
+    metadata: Box<TaskMetadata>,
     inner: Caller<R>,
+    batch: Option<BatchSlot>,
+    then: Option<Continuation<R>>,
+    queued_at: Instant,
+    #[cfg(feature = "serde")]
+    spec: Option<Box<TaskSpec>>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// The value produced by a task along with the information about the task.
+///
+/// The value is `None` if it was passed to the next task in the chain.
+pub(crate) struct TaskOutput<R> {
+    pub(crate) batch: Option<BatchSlot>,
+    pub(crate) value: Option<R>,
+    pub(crate) outcome: TaskOutcome,
+}
+
+/// Information about the task that produced a result.
+///
+/// It's passed to the handlers created with [`Handler::with_outcome`](crate::Handler::with_outcome).
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    id: TaskId,
+    name: String,
+    metadata: TaskMetadata,
+    batch: Option<BatchId>,
+    started_at: Instant,
+    duration: Duration,
+}
+
+impl TaskOutcome {
+    /// Id of the task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Task's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Task's metadata.
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.metadata
+    }
+
+    /// Id of the batch the task belongs to.
+    pub fn batch_id(&self) -> Option<BatchId> {
+        self.batch
+    }
+
+    /// The moment when the task started its execution.
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /// Time it took to execute the task.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Decides what happens with the task's result.
+type Continuation<R> = Box<dyn FnOnce(R) -> Outcome<R> + Send>;
+
+/// Sends the result to the task's collection, `None` if it was used by the next task.
+type Deliver<R> = Box<dyn FnOnce(Option<R>) + Send>;
+
+enum Outcome<R> {
+    /// The result is delivered to the task's collection.
+    Deliver(R),
+    /// The result is used to create a task in another collection.
+    Spawn(Box<dyn FnOnce(Deliver<R>) -> Spawn + Send>),
 }
 
 impl<R: 'static + Send> Task<R> {
     /// Creates a new task using provided name and [`Caller`](crate::Caller).
     pub fn new(name: impl Into<String>, caller: Caller<R>) -> Self {
         Self {
+            id: TaskId::next(),
             name: name.into(),
+            metadata: Box::default(),
             inner: caller,
+            batch: None,
+            then: None,
+            queued_at: Instant::now(),
+            #[cfg(feature = "serde")]
+            spec: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
         }
     }
 
-    /// Executes the task using provided `Sender` to send the result.
-    pub(crate) fn execute(self, channel: Sender<R>) -> TaskData {
-        let (fut, progress) = match self.inner {
-            Caller::Standard(fut) => (fut, None),
-            Caller::Progressing(fun) => {
-                let task_progress = TaskProgress::new();
-                let fut = (fun)(task_progress.share());
+    /// Sets task's description. It's shown as a tooltip in the ui.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.metadata.set_description(description);
+        self
+    }
 
-                (fut, Some(task_progress))
-            }
+    /// Adds a tag to the task.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.metadata.add_tag(tag);
+        self
+    }
+
+    /// Adds several tags to the task.
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        for tag in tags {
+            self.metadata.add_tag(tag);
+        }
+        self
+    }
+
+    /// Sets task's icon, e.g. an emoji.
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.metadata.set_icon(icon);
+        self
+    }
+
+    /// Sets task's category.
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.metadata.set_category(category);
+        self
+    }
+
+    /// Sets the user data of type `T`. See [`TaskMetadata::user_data`].
+    pub fn with_user_data<T: Any + Send + Sync>(mut self, data: T) -> Self {
+        self.metadata.insert_user_data(data);
+        self
+    }
+
+    /// Feeds the result of this task into a new task that is pushed to collection `B`
+    /// when this task completes.
+    ///
+    /// The result is not delivered to this task's collection unless collection `B`
+    /// doesn't exist when the task completes. If the task is cancelled the chain stops.
+    /// Chained tasks can have their own continuations. The continuation is dropped
+    /// if the task is added to a [`Batch`](crate::Batch).
+    ///
+    /// ```rust
+    /// # use egui_task_manager::*;
+    /// # struct Parse;
+    /// # impl<'c> TasksCollection<'c> for Parse {
+    /// #     type Context = ();
+    /// #     type Target = usize;
+    /// #     type Executor = executors::Parallel;
+    /// #     fn name() -> &'static str { "Parse" }
+    /// #     fn handle(_context: Self::Context) -> Handler<'c, Self::Target> { Handler::new(|_| ()) }
+    /// # }
+    /// let task = Task::new("Fetch", Caller::standard(async { "content".to_owned() }))
+    ///     .then::<Parse>(|content| Task::new("Parse", Caller::standard(async move { content.len() })));
+    /// ```
+    pub fn then<'c, B>(self, next: impl FnOnce(R) -> Task<B::Target> + Send + 'static) -> Self
+    where
+        B: TasksCollection<'c> + 'static,
+    {
+        let continuation = move |value| {
+            Outcome::Spawn(Box::new(move |deliver| {
+                Spawn::chain::<B, R>(value, next, deliver)
+            }))
         };
 
-        let is_finished = Arc::new(OnceLock::new());
+        Self {
+            then: Some(Box::new(continuation)),
+            ..self
+        }
+    }
 
-        let cloned_is_finished = is_finished.clone();
+    /// Converts the task into the one that sends its result using `channel`.
+    ///
+    /// Tasks created by the continuation are sent using `spawns`. If the next
+    /// collection can't be reached the result is sent using `channel` instead.
+    pub(crate) fn bind(
+        mut self,
+        channel: Sender<TaskOutput<R>>,
+        spawns: Option<Sender<Spawn>>,
+    ) -> AnyTask {
+        let then = self.then.take();
+        let batch = self.batch;
 
-        let handle = TaskHandle::from(async move {
+        self.finish_with(move |value, outcome| {
+            let deliver = move |value| {
+                let _ = channel.send(TaskOutput {
+                    batch,
+                    value,
+                    outcome,
+                });
+            };
+
+            let next = match then {
+                Some(then) => (then)(value),
+                None => Outcome::Deliver(value),
+            };
+            match next {
+                Outcome::Deliver(value) => deliver(Some(value)),
+                Outcome::Spawn(spawn) => {
+                    let spawn = spawn(Box::new(deliver));
+                    match spawns {
+                        Some(spawns) => {
+                            if let Err(SendError(spawn)) = spawns.send(spawn) {
+                                spawn.discard();
+                            }
+                        }
+                        None => spawn.discard(),
+                    }
+                }
+            }
+        })
+    }
+
+    /// Converts the task into the one that sends its result to the returned [`Promise`].
+    /// The task's continuation is dropped.
+    pub(crate) fn into_promise(self) -> (AnyTask, Promise<R>) {
+        let (sender, receiver) = oneshot::channel();
+        let task = self.finish_with(move |value, _| {
+            let _ = sender.send(value);
+        });
+        (task, Promise::new(receiver))
+    }
+
+    /// Converts the task into the one that calls `finish` with the result.
+    fn finish_with(self, finish: impl FnOnce(R, TaskOutcome) + Send + 'static) -> AnyTask {
+        let mut outcome = TaskOutcome {
+            id: self.id,
+            name: self.name.clone(),
+            metadata: TaskMetadata::clone(&self.metadata),
+            batch: self.batch.map(|slot| slot.id),
+            started_at: Instant::now(),
+            duration: Duration::ZERO,
+        };
+        let inner = self.inner.map_future(|fut| async move {
+            outcome.started_at = Instant::now();
             let value = fut.await;
-            let _ = channel.send(value);
-            let _ = cloned_is_finished.clone().set(Finished);
+            outcome.duration = outcome.started_at.elapsed();
+            (finish)(value, outcome)
         });
 
-        TaskData {
+        Task {
+            id: self.id,
             name: self.name,
-            handle,
-            is_finished,
-            progress,
+            metadata: self.metadata,
+            inner,
+            batch: self.batch,
+            then: None,
+            queued_at: self.queued_at,
+            #[cfg(feature = "serde")]
+            spec: self.spec,
+            #[cfg(feature = "tracing")]
+            span: self.span,
         }
     }
-}
 
-impl<T> HigherKinded for Task<T> {
-    type T<A> = Task<A>;
+    /// Task's id.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Task's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Task's metadata.
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.metadata
+    }
+
+    /// Mutable reference to the task's metadata.
+    pub fn metadata_mut(&mut self) -> &mut TaskMetadata {
+        &mut self.metadata
+    }
+
+    #[cfg(feature = "serde")]
+    /// The spec the task was created from, see [`TaskManager::push_spec`](crate::TaskManager::push_spec).
+    pub fn spec(&self) -> Option<&TaskSpec> {
+        self.spec.as_deref()
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn with_spec(mut self, spec: TaskSpec) -> Self {
+        self.spec = Some(Box::new(spec));
+        self
+    }
+
+    /// Adds the task to the batch. The continuation is dropped, the result
+    /// belongs to the batch.
+    pub(crate) fn with_batch(self, slot: BatchSlot) -> Self {
+        Self {
+            batch: Some(slot),
+            then: None,
+            ..self
+        }
+    }
+
+    pub(crate) fn batch_slot(&self) -> Option<BatchSlot> {
+        self.batch
+    }
 }
 
-impl<T> IntoAny for Task<T>
+impl<T, E> Task<Result<T, E>>
 where
     T: Send + 'static,
+    E: Send + 'static,
 {
-    fn into_any(self) -> Self::T<Box<dyn Any + Send>> {
-        Task {
+    /// Same as [`then`](Self::then) but only `Ok` values are passed to the next task.
+    ///
+    /// Errors are delivered to this task's collection and the chain stops.
+    pub fn and_then<'c, B>(self, next: impl FnOnce(T) -> Task<B::Target> + Send + 'static) -> Self
+    where
+        B: TasksCollection<'c> + 'static,
+    {
+        let continuation = move |result| match result {
+            Ok(value) => Outcome::Spawn(Box::new(move |deliver: Deliver<Result<T, E>>| {
+                Spawn::chain::<B, T>(value, next, move |value| deliver(value.map(Ok)))
+            })),
+            Err(err) => Outcome::Deliver(Err(err)),
+        };
+
+        Self {
+            then: Some(Box::new(continuation)),
+            ..self
+        }
+    }
+}
+
+impl AnyTask {
+    /// Remembers the moment when the task was pushed to the executor.
+    pub(crate) fn mark_queued(&mut self) {
+        self.queued_at = Instant::now();
+    }
+
+    /// Time the task has been waiting in the executor.
+    pub(crate) fn waited(&self) -> Duration {
+        self.queued_at.elapsed()
+    }
+
+    #[cfg(feature = "tracing")]
+    /// Creates the span the task runs in.
+    pub(crate) fn set_span(&mut self, collection: &str) {
+        self.span = instrument::task_span(collection, self.id, &self.name);
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
+    /// Executes the task.
+    ///
+    /// The task's future is suspended while the `gate` is paused.
+    pub(crate) fn execute(self, gate: &PauseGate) -> TaskData {
+        let questions = TaskQuestions::new();
+
+        let (fut, progress, log) = match self.inner {
+            Caller::Standard(fut) => (fut, None, None),
+            Caller::Progressing(fun) => {
+                let log = TaskLog::default();
+                let task_progress = TaskProgress::new();
+                let shared = task_progress
+                    .share()
+                    .with_log(log.sender())
+                    .with_questions(questions.sender());
+                let fut = (fun)(shared);
+
+                (fut, Some(task_progress), Some(log))
+            }
+        };
+
+        let fut = CatchUnwind::new(gate.wrap(fut));
+        #[cfg(feature = "tracing")]
+        let fut = tracing::Instrument::instrument(fut, self.span.clone());
+        let is_finished = Arc::new(OnceLock::new());
+        let error = Arc::new(OnceLock::new());
+
+        let cloned_is_finished = is_finished.clone();
+        let cloned_error = error.clone();
+
+        let started_at = Instant::now();
+        let handle = TaskHandle::from(async move {
+            let finished = match fut.await {
+                Ok(()) => Finished::Completed,
+                Err(message) => {
+                    let _ = cloned_error.set(message);
+                    Finished::Failed
+                }
+            };
+            let _ = cloned_is_finished.set((finished, Instant::now()));
+        });
+
+        TaskData {
+            id: self.id,
             name: self.name,
-            inner: self.inner.into_any(),
+            metadata: *self.metadata,
+            batch: self.batch,
+            started_at,
+            handle,
+            is_finished,
+            error,
+            progress,
+            log,
+            questions,
+            #[cfg(feature = "serde")]
+            spec: self.spec,
+            #[cfg(feature = "tracing")]
+            span: self.span,
+            #[cfg(feature = "tracing")]
+            milestone: 0,
         }
     }
 }
 
 /// The data of a task that is currently running.
 pub struct TaskData {
+    id: TaskId,
     name: String,
+    metadata: TaskMetadata,
+    batch: Option<BatchSlot>,
+    started_at: Instant,
     handle: TaskHandle,
-    is_finished: Arc<OnceLock<Finished>>,
+    is_finished: Arc<OnceLock<(Finished, Instant)>>,
+    error: Arc<OnceLock<String>>,
     progress: Option<TaskProgress>,
+    log: Option<TaskLog>,
+    questions: TaskQuestions,
+    #[cfg(feature = "serde")]
+    spec: Option<Box<TaskSpec>>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    milestone: u8,
 }
 
 impl TaskData {
     #[cfg(feature = "egui")]
     /// Draws a simple ui.
     pub fn ui(&self, ui: &mut egui::Ui) {
-        ui.label(self.name.as_str());
+        use crate::execution::format_duration;
+
+        ui.horizontal(|ui| {
+            if let Some(icon) = self.metadata.icon() {
+                ui.label(icon);
+            }
+            let name = ui.label(self.name.as_str());
+            if let Some(description) = self.metadata.description() {
+                name.on_hover_text(description);
+            }
+            if let Some(category) = self.metadata.category() {
+                ui.weak(category);
+            }
+            ui.weak(format_duration(self.elapsed()));
+        });
+        self.metadata.tags_ui(ui);
         match self.progress.as_ref() {
-            Some(progress) => progress.ui(ui),
+            Some(progress) => {
+                ui.push_id(self.id, |ui| progress.ui(ui));
+            }
             None => {
                 ui.spinner();
             }
         }
 
+        ui.push_id(("task_questions", self.id), |ui| {
+            for question in self.questions.pending() {
+                ui.group(|ui| question.ui(ui));
+            }
+        });
+
+        if let Some(log) = self.log.as_ref().filter(|log| !log.is_empty()) {
+            egui::CollapsingHeader::new(format!("Log ({})", log.len()))
+                .id_source(("task_log", self.id))
+                .show(ui, |ui| log.ui(ui));
+        }
+
         let button = ui.button("Cancel");
         let popup_id = egui::Id::new("confirm_task_cancellation_popup_id")
             .with(self.name())
@@ -112,8 +540,7 @@ impl TaskData {
                 ui.label("Are you sure you want to cancel the task?");
                 ui.horizontal(|ui| {
                     if ui.button("Yes").clicked() {
-                        self.handle.abort();
-                        let _ = self.is_finished.set(Finished);
+                        self.cancel();
                     };
                     if ui.button("No").clicked() {};
                 });
@@ -121,11 +548,60 @@ impl TaskData {
         );
     }
 
+    /// Task's id.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
     /// Task's name
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Task's metadata.
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.metadata
+    }
+
+    /// The moment when the task started its execution.
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /// Time elapsed since the task started its execution.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// The moment when the task finished, was cancelled or panicked.
+    pub fn finished_at(&self) -> Option<Instant> {
+        self.is_finished.get().map(|(_, finished_at)| *finished_at)
+    }
+
+    /// Time the task was running.
+    ///
+    /// Unlike [`elapsed`](Self::elapsed) it stops growing once the task is finished.
+    pub fn duration(&self) -> Duration {
+        self.finished_at().map_or_else(
+            || self.elapsed(),
+            |finished_at| finished_at - self.started_at,
+        )
+    }
+
+    /// Smoothed progress rate in units per second.
+    ///
+    /// Returns `None` if the task has no progress or no updates were received yet.
+    pub fn rate(&self) -> Option<f64> {
+        self.progress.as_ref().and_then(TaskProgress::rate)
+    }
+
+    /// Estimated time remaining.
+    ///
+    /// Returns `None` if the task has no progress, its total or rate is unknown.
+    pub fn eta(&self) -> Option<Duration> {
+        self.progress.as_ref().and_then(TaskProgress::eta)
+    }
+
     /// Task's handler.
     ///
     /// Can be used to abort the task execution.
@@ -133,11 +609,45 @@ impl TaskData {
         &self.handle
     }
 
+    /// Aborts the task's execution and marks it as finished.
+    pub fn cancel(&self) {
+        self.handle.abort();
+        let _ = self.is_finished.set((Finished::Cancelled, Instant::now()));
+    }
+
     /// Checks if the task finished or not.
     pub fn is_finished(&self) -> bool {
         self.is_finished.get().is_some()
     }
 
+    /// Checks if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.finished() == Some(Finished::Cancelled)
+    }
+
+    /// Checks if the task panicked.
+    pub fn is_failed(&self) -> bool {
+        self.finished() == Some(Finished::Failed)
+    }
+
+    fn finished(&self) -> Option<Finished> {
+        self.is_finished.get().map(|(finished, _)| *finished)
+    }
+
+    /// The panic message of the failed task.
+    pub fn error(&self) -> Option<&str> {
+        self.error.get().map(String::as_str)
+    }
+
+    /// Id of the batch the task belongs to.
+    pub fn batch_id(&self) -> Option<BatchId> {
+        self.batch.map(|slot| slot.id)
+    }
+
+    pub(crate) fn batch_slot(&self) -> Option<BatchSlot> {
+        self.batch
+    }
+
     /// Returns a reference to the [`TaskProgress`](crate::TaskProgress) of the current task if exists.
     pub fn progress(&self) -> Option<&TaskProgress> {
         self.progress.as_ref()
@@ -147,4 +657,137 @@ impl TaskData {
     pub fn progress_mut(&mut self) -> Option<&mut TaskProgress> {
         self.progress.as_mut()
     }
+
+    /// Task's log. Only [`Progressing`](crate::Caller::Progressing) tasks have it.
+    ///
+    /// Lines are sent using [`TaskProgressShared::log`](crate::TaskProgressShared::log).
+    pub fn log(&self) -> Option<&TaskLog> {
+        self.log.as_ref()
+    }
+
+    /// Mutable reference to the task's log if exists.
+    pub fn log_mut(&mut self) -> Option<&mut TaskLog> {
+        self.log.as_mut()
+    }
+
+    /// Questions asked by the task that are waiting for the answer.
+    ///
+    /// See [`TaskProgressShared::ask`](crate::TaskProgressShared::ask).
+    pub fn questions(&self) -> &[Question] {
+        self.questions.pending()
+    }
+
+    /// Mutable reference to the task's questions.
+    pub fn questions_mut(&mut self) -> &mut TaskQuestions {
+        &mut self.questions
+    }
+
+    #[cfg(feature = "serde")]
+    /// The spec the task was created from, see [`TaskManager::push_spec`](crate::TaskManager::push_spec).
+    pub fn spec(&self) -> Option<&TaskSpec> {
+        self.spec.as_deref()
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
+    #[cfg(feature = "tracing")]
+    /// Emits a progress event when the task reaches the next milestone.
+    pub(crate) fn trace_progress(&mut self) {
+        let Some(progress) = &self.progress else {
+            return;
+        };
+
+        let state = progress.combined_state();
+        let milestone = instrument::milestone(&state);
+        if milestone > self.milestone {
+            self.milestone = milestone;
+            instrument::trace(&self.span, &TaskEventKind::Progress(&state), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::poll_until, *};
+
+    struct Fetch;
+
+    impl<'c> TasksCollection<'c> for Fetch {
+        type Context = &'c mut Option<String>;
+        type Target = String;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Fetch"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|content| *context = Some(content))
+        }
+    }
+
+    struct Parse;
+
+    impl<'c> TasksCollection<'c> for Parse {
+        type Context = &'c mut Option<usize>;
+        type Target = usize;
+        type Executor = executors::Parallel;
+
+        fn name() -> &'static str {
+            "Parse"
+        }
+
+        fn handle(context: Self::Context) -> Handler<'c, Self::Target> {
+            Handler::new(|len| *context = Some(len))
+        }
+    }
+
+    fn fetch() -> Task<String> {
+        Task::new("Fetch", Caller::standard(async { "content".to_owned() })).then::<Parse>(
+            |content| Task::new("Parse", Caller::standard(async move { content.len() })),
+        )
+    }
+
+    #[test]
+    fn then_pushes_the_next_task() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager
+            .register_collection::<Fetch>()
+            .register_collection::<Parse>();
+        manager.push_task::<Fetch>(fetch());
+
+        let (mut fetched, mut parsed) = (None, None);
+        poll_until(|| {
+            let contexts = Contexts::new()
+                .with::<Fetch>(&mut fetched)
+                .with::<Parse>(&mut parsed);
+            manager.process(contexts);
+            parsed.is_some()
+        });
+
+        assert_eq!(parsed, Some(7));
+        assert_eq!(fetched, None);
+    }
+
+    #[test]
+    fn then_delivers_to_the_source_without_the_next_collection() {
+        crate::setup!();
+
+        let mut manager = TaskManager::new();
+        manager.register_collection::<Fetch>();
+        manager.push_task::<Fetch>(fetch());
+
+        let mut fetched = None;
+        poll_until(|| {
+            manager.process(Contexts::new().with::<Fetch>(&mut fetched));
+            fetched.is_some()
+        });
+
+        assert_eq!(fetched.as_deref(), Some("content"));
+    }
 }
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use metrics::Label;
+
+use crate::history::TaskStatus;
+
+const RUNNING: &str = "egui_task_manager_running_tasks";
+const QUEUED: &str = "egui_task_manager_queued_tasks";
+const COMPLETED: &str = "egui_task_manager_tasks_completed_total";
+const FAILED: &str = "egui_task_manager_tasks_failed_total";
+const CANCELLED: &str = "egui_task_manager_tasks_cancelled_total";
+const DURATION: &str = "egui_task_manager_task_duration_seconds";
+const QUEUE_WAIT: &str = "egui_task_manager_task_queue_wait_seconds";
+
+/// Labels that identify the collection, its name and its key in the manager.
+///
+/// Dynamic collections can share a name, so the key keeps their metrics apart.
+pub(crate) fn labels(collection: &str, key: &str) -> Vec<Label> {
+    vec![
+        Label::new("collection", collection.to_owned()),
+        Label::new("key", key.to_owned()),
+    ]
+}
+
+/// Records the time the task spent in the queue before it was started.
+pub(crate) fn started(labels: &[Label], wait: Duration) {
+    metrics::histogram!(QUEUE_WAIT, labels.to_vec()).record(wait);
+}
+
+/// Records a finished task. `duration` is `None` if the task was cancelled before it started.
+pub(crate) fn finished(labels: &[Label], status: TaskStatus, duration: Option<Duration>) {
+    let name = match status {
+        TaskStatus::Completed => COMPLETED,
+        TaskStatus::Cancelled => CANCELLED,
+        TaskStatus::Failed => FAILED,
+    };
+    metrics::counter!(name, labels.to_vec()).increment(1);
+
+    if let Some(duration) = duration {
+        let mut labels = labels.to_vec();
+        labels.push(Label::new("status", status.to_string()));
+        metrics::histogram!(DURATION, labels).record(duration);
+    }
+}
+
+/// Sets the number of running and queued tasks of the collection.
+pub(crate) fn set_task_counts(labels: &[Label], running: usize, queued: usize) {
+    metrics::gauge!(RUNNING, labels.to_vec()).set(running as f64);
+    metrics::gauge!(QUEUED, labels.to_vec()).set(queued as f64);
+}
@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+/// Calls `done` until it returns `true`, e.g. after processing the manager.
+///
+/// Panics if it doesn't happen within five seconds.
+pub(crate) fn poll_until(mut done: impl FnMut() -> bool) {
+    for _ in 0..500 {
+        if done() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    panic!("the condition was not met in time");
+}